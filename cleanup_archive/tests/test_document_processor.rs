@@ -22,7 +22,7 @@ async fn test_document_creation() {
 async fn test_document_processor() {
     let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
     
-    let processor = match DocumentProcessor::new(&redis_url) {
+    let processor = match DocumentProcessor::new(&redis_url).await {
         Ok(p) => p,
         Err(e) => panic!("Failed to create processor: {}", e),
     };
@@ -48,7 +48,7 @@ async fn test_document_processor() {
 
 #[tokio::test]
 async fn test_error_handling() {
-    let processor = DocumentProcessor::new("redis://invalid-host:6379").unwrap();
+    let processor = DocumentProcessor::new("redis://invalid-host:6379").await.unwrap();
     
     let doc = Document {
         id: "test-error".to_string(),
@@ -72,7 +72,7 @@ async fn test_error_handling() {
 
 #[tokio::test]
 async fn test_pdf_processing() {
-    let processor = DocumentProcessor::new("redis://127.0.0.1:6379").unwrap();
+    let processor = DocumentProcessor::new("redis://127.0.0.1:6379").await.unwrap();
     
     // Create a minimal PDF content
     let pdf_content = include_bytes!("../test_data/sample.pdf");
@@ -98,7 +98,7 @@ async fn test_pdf_processing() {
 
 #[tokio::test]
 async fn test_large_document_handling() {
-    let processor = DocumentProcessor::new("redis://127.0.0.1:6379").unwrap();
+    let processor = DocumentProcessor::new("redis://127.0.0.1:6379").await.unwrap();
     
     // Create a large document (1MB)
     let large_content = vec![b'a'; 1_000_000];
@@ -0,0 +1,86 @@
+use nocturnal_archive::queue_handler::QueueMode;
+use nocturnal_archive::{QueueHandler, QueueItem};
+use std::time::Duration;
+
+fn redis_client() -> redis::Client {
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    redis::Client::open(redis_url).unwrap()
+}
+
+#[tokio::test]
+async fn test_dequeue_reliable_recovers_item_after_worker_crash() {
+    let handler = QueueHandler::new(
+        redis_client(),
+        "test_queue_reap_processing".to_string(),
+        "test_queue_reap_retry".to_string(),
+        3,
+        QueueMode::List,
+    )
+    .await
+    .unwrap();
+
+    let item = QueueItem {
+        id: "crash-test-1".to_string(),
+        priority: 0,
+        retry_count: 0,
+        payload: serde_json::json!({"foo": "bar"}),
+    };
+    handler.enqueue(item).await.unwrap();
+
+    // Worker dequeues reliably, then "crashes" without ever acking.
+    let (dequeued, _handle) = handler
+        .dequeue_reliable("crashed-worker")
+        .await
+        .unwrap()
+        .expect("item should be dequeued");
+    assert_eq!(dequeued.id, "crash-test-1");
+
+    // Once the visibility timeout elapses, reap_stale should recover the
+    // item through the normal retry path instead of losing it.
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    let reaped = handler.reap_stale(Duration::from_millis(500)).await.unwrap();
+    assert_eq!(reaped, 1);
+
+    let (processing, retry) = handler.get_queue_length().await.unwrap();
+    assert_eq!(processing, 0);
+    assert_eq!(retry, 1);
+}
+
+#[tokio::test]
+async fn test_ack_prevents_reap_stale_from_recovering_item() {
+    let handler = QueueHandler::new(
+        redis_client(),
+        "test_queue_ack_processing".to_string(),
+        "test_queue_ack_retry".to_string(),
+        3,
+        QueueMode::List,
+    )
+    .await
+    .unwrap();
+
+    let item = QueueItem {
+        id: "ack-test-1".to_string(),
+        priority: 0,
+        retry_count: 0,
+        payload: serde_json::json!({"foo": "bar"}),
+    };
+    handler.enqueue(item).await.unwrap();
+
+    let (dequeued, handle) = handler
+        .dequeue_reliable("well-behaved-worker")
+        .await
+        .unwrap()
+        .expect("item should be dequeued");
+    assert_eq!(dequeued.id, "ack-test-1");
+
+    handler.ack(handle).await.unwrap();
+
+    // Already acked, so there's nothing left for reap_stale to recover.
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    let reaped = handler.reap_stale(Duration::from_millis(500)).await.unwrap();
+    assert_eq!(reaped, 0);
+
+    let (processing, retry) = handler.get_queue_length().await.unwrap();
+    assert_eq!(processing, 0);
+    assert_eq!(retry, 0);
+}
@@ -1,7 +1,13 @@
+use async_trait::async_trait;
+use bytes::Bytes;
 use dashmap::DashMap;
+use futures::{Stream, StreamExt};
+use metrics::{counter, gauge, histogram};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::time::sleep;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedItem<T> {
@@ -14,7 +20,7 @@ impl<T> CachedItem<T> {
     pub fn new(data: T, ttl: Option<Duration>) -> Self {
         let now = Instant::now();
         let expires_at = ttl.map(|duration| now + duration);
-        
+
         Self {
             data,
             created_at: now,
@@ -31,140 +37,331 @@ impl<T> CachedItem<T> {
     }
 }
 
-pub struct ResponseCache {
-    cache: DashMap<String, CachedItem<String>>,
-    default_ttl: Duration,
-    cleanup_interval: Duration,
+/// A cached value. Small payloads are held fully in memory as `Text`/`Bytes`;
+/// large document bodies can be cached as a `ByteStream` (with an optional
+/// known length) so they pass through the cache without being buffered in
+/// full. A `ByteStream` can only be read once — whichever `get` receives it
+/// takes ownership of the underlying stream.
+pub enum CacheData {
+    Text(String),
+    Bytes(Vec<u8>),
+    ByteStream(
+        Pin<Box<dyn Stream<Item = anyhow::Result<Bytes>> + Send>>,
+        Option<u64>,
+    ),
 }
 
-impl ResponseCache {
-    pub fn new() -> Self {
-        let cache = DashMap::new();
-        let default_ttl = Duration::from_secs(3600); // 1 hour
-        let cleanup_interval = Duration::from_secs(300); // 5 minutes
-
-        let cache_instance = Self {
-            cache,
-            default_ttl,
-            cleanup_interval,
-        };
+impl fmt::Debug for CacheData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheData::Text(s) => f.debug_tuple("Text").field(&s.len()).finish(),
+            CacheData::Bytes(b) => f.debug_tuple("Bytes").field(&b.len()).finish(),
+            CacheData::ByteStream(_, len) => f.debug_tuple("ByteStream").field(len).finish(),
+        }
+    }
+}
 
-        // Start cleanup task
-        let cache_clone = cache_instance.cache.clone();
-        let cleanup_interval = cache_instance.cleanup_interval;
-        
-        tokio::spawn(async move {
-            loop {
-                sleep(cleanup_interval).await;
-                Self::cleanup_expired(&cache_clone);
+impl CacheData {
+    /// Drain the value into a single in-memory buffer, reading a
+    /// `ByteStream` to completion if that's what's stored.
+    async fn into_bytes(self) -> anyhow::Result<Vec<u8>> {
+        match self {
+            CacheData::Text(s) => Ok(s.into_bytes()),
+            CacheData::Bytes(b) => Ok(b),
+            CacheData::ByteStream(mut stream, _) => {
+                let mut buf = Vec::new();
+                while let Some(chunk) = stream.next().await {
+                    buf.extend_from_slice(&chunk?);
+                }
+                Ok(buf)
             }
-        });
+        }
+    }
+}
 
-        cache_instance
+/// Storage for cached values, abstracted behind a trait so callers can swap
+/// an in-process store for a shared one without touching call sites.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    async fn get(&self, key: &str) -> Option<CacheData>;
+    /// Store `value` under `key`, returning whether a live (non-expired)
+    /// entry already occupied that key — so callers can keep an external
+    /// entry-count gauge accurate across overwrites instead of counting
+    /// every `set` as a brand-new entry.
+    async fn set(&self, key: &str, value: CacheData) -> bool;
+    async fn set_with_ttl(&self, key: &str, value: CacheData, ttl: Duration) -> bool;
+    /// Remove `key`, returning whether an entry was actually there to
+    /// remove.
+    async fn remove(&self, key: &str) -> bool;
+    async fn contains_key(&self, key: &str) -> bool;
+}
+
+/// In-process backend built on `DashMap`, expiring entries lazily on access
+/// via `CachedItem::is_expired`. `Text`/`Bytes` values are cloned out on
+/// read and stay in the map so they can be read again; a `ByteStream` is
+/// removed from the map on read, since the stream can't be replayed.
+pub struct DashMapBackend {
+    cache: DashMap<String, CachedItem<CacheData>>,
+    default_ttl: Duration,
+}
+
+impl DashMapBackend {
+    pub fn new(default_ttl: Duration) -> Self {
+        Self {
+            cache: DashMap::new(),
+            default_ttl,
+        }
     }
+}
 
-    pub async fn get<T>(&self, key: &str) -> Option<T>
-    where
-        T: for<'de> Deserialize<'de> + Clone,
-    {
-        if let Some(cached_item) = self.cache.get(key) {
-            if cached_item.is_expired() {
-                // Remove expired item
+impl Default for DashMapBackend {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(3600))
+    }
+}
+
+#[async_trait]
+impl CacheBackend for DashMapBackend {
+    async fn get(&self, key: &str) -> Option<CacheData> {
+        // Read via a non-removing `get` and clone `Text`/`Bytes` out from
+        // under the guard, so a concurrent `get()` on the same key never
+        // observes the key as absent. A `ByteStream` can't be cloned (it's
+        // consumed on read), so it's taken out via `remove` instead.
+        {
+            let entry = self.cache.get(key)?;
+            if entry.is_expired() {
+                drop(entry);
                 self.cache.remove(key);
                 return None;
             }
 
-            // Try to deserialize the cached data
-            if let Ok(data) = serde_json::from_str::<T>(&cached_item.data) {
-                return Some(data);
+            match &entry.data {
+                CacheData::Text(s) => return Some(CacheData::Text(s.clone())),
+                CacheData::Bytes(b) => return Some(CacheData::Bytes(b.clone())),
+                CacheData::ByteStream(..) => {}
             }
         }
-        None
+
+        let (_, item) = self.cache.remove(key)?;
+        if item.is_expired() {
+            return None;
+        }
+        Some(item.data)
     }
 
-    pub async fn set<T>(&self, key: &str, value: &T)
-    where
-        T: Serialize,
-    {
-        if let Ok(serialized) = serde_json::to_string(value) {
-            let cached_item = CachedItem::new(serialized, Some(self.default_ttl));
-            self.cache.insert(key.to_string(), cached_item);
+    async fn set(&self, key: &str, value: CacheData) -> bool {
+        self.set_with_ttl(key, value, self.default_ttl).await
+    }
+
+    async fn set_with_ttl(&self, key: &str, value: CacheData, ttl: Duration) -> bool {
+        let previous = self
+            .cache
+            .insert(key.to_string(), CachedItem::new(value, Some(ttl)));
+        previous.is_some_and(|item| !item.is_expired())
+    }
+
+    async fn remove(&self, key: &str) -> bool {
+        self.cache
+            .remove(key)
+            .is_some_and(|(_, item)| !item.is_expired())
+    }
+
+    async fn contains_key(&self, key: &str) -> bool {
+        match self.cache.get(key) {
+            Some(item) if item.is_expired() => {
+                drop(item);
+                self.cache.remove(key);
+                false
+            }
+            Some(_) => true,
+            None => false,
         }
     }
+}
 
-    pub async fn set_with_ttl<T>(&self, key: &str, value: &T, ttl: Duration)
-    where
-        T: Serialize,
-    {
-        if let Ok(serialized) = serde_json::to_string(value) {
-            let cached_item = CachedItem::new(serialized, Some(ttl));
-            self.cache.insert(key.to_string(), cached_item);
+/// Redis-backed implementation, for sharing cached responses across
+/// instances. TTL is enforced by Redis's own key expiry (`SET ... EX`)
+/// rather than the `expires_at` check `DashMapBackend` relies on. Values
+/// always come back as `CacheData::Bytes`, since Redis doesn't preserve
+/// whether the original payload was text or raw bytes.
+pub struct RedisBackend {
+    client: redis::Client,
+    default_ttl: Duration,
+}
+
+impl RedisBackend {
+    pub fn new(client: redis::Client, default_ttl: Duration) -> Self {
+        Self {
+            client,
+            default_ttl,
         }
     }
 
-    pub async fn remove(&self, key: &str) {
-        self.cache.remove(key);
+    async fn connection(&self) -> anyhow::Result<redis::aio::ConnectionManager> {
+        Ok(redis::aio::ConnectionManager::new(self.client.clone()).await?)
     }
+}
 
-    pub async fn clear(&self) {
-        self.cache.clear();
+#[async_trait]
+impl CacheBackend for RedisBackend {
+    async fn get(&self, key: &str) -> Option<CacheData> {
+        let mut conn = self.connection().await.ok()?;
+        let bytes: Option<Vec<u8>> = redis::AsyncCommands::get(&mut conn, key).await.ok()?;
+        bytes.map(CacheData::Bytes)
     }
 
-    pub async fn size(&self) -> usize {
-        self.cache.len()
+    async fn set(&self, key: &str, value: CacheData) -> bool {
+        self.set_with_ttl(key, value, self.default_ttl).await
     }
 
-    pub async fn keys(&self) -> Vec<String> {
-        self.cache
-            .iter()
-            .map(|entry| entry.key().clone())
-            .collect()
+    async fn set_with_ttl(&self, key: &str, value: CacheData, ttl: Duration) -> bool {
+        let Ok(mut conn) = self.connection().await else {
+            return false;
+        };
+        let Ok(bytes) = value.into_bytes().await else {
+            return false;
+        };
+        // `SET key val EX ttl GET` in a single round trip, so a concurrent
+        // set on the same brand-new key can't have both callers observe
+        // `existed == false` the way a separate `EXISTS` + `SET_EX` could —
+        // mirrors the atomicity `DashMapBackend::set_with_ttl` gets for free
+        // from `DashMap::insert`'s return value.
+        let previous: Option<Vec<u8>> = redis::cmd("SET")
+            .arg(key)
+            .arg(bytes)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .arg("GET")
+            .query_async(&mut conn)
+            .await
+            .unwrap_or(None);
+        previous.is_some()
     }
 
-    pub async fn contains_key(&self, key: &str) -> bool {
-        self.cache.contains_key(key)
+    async fn remove(&self, key: &str) -> bool {
+        match self.connection().await {
+            Ok(mut conn) => redis::AsyncCommands::del::<_, i64>(&mut conn, key)
+                .await
+                .unwrap_or(0)
+                > 0,
+            Err(_) => false,
+        }
     }
 
-    pub async fn get_stats(&self) -> CacheStats {
-        let total_items = self.cache.len();
-        let mut expired_items = 0;
-        let mut valid_items = 0;
+    async fn contains_key(&self, key: &str) -> bool {
+        match self.connection().await {
+            Ok(mut conn) => redis::AsyncCommands::exists(&mut conn, key)
+                .await
+                .unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+}
 
-        for entry in self.cache.iter() {
-            if entry.is_expired() {
-                expired_items += 1;
-            } else {
-                valid_items += 1;
+/// Cache for scraped/processed responses, backed by a pluggable
+/// `CacheBackend` (`DashMapBackend` in-process, or `RedisBackend` to share
+/// entries across instances).
+pub struct ResponseCache {
+    backend: Arc<dyn CacheBackend>,
+}
+
+impl ResponseCache {
+    pub fn new() -> Self {
+        Self::with_backend(Arc::new(DashMapBackend::default()))
+    }
+
+    /// Back this cache with Redis instead of the in-process `DashMap`, so
+    /// entries are visible to every process sharing `client`.
+    pub fn with_redis(client: redis::Client, default_ttl: Duration) -> Self {
+        Self::with_backend(Arc::new(RedisBackend::new(client, default_ttl)))
+    }
+
+    pub fn with_backend(backend: Arc<dyn CacheBackend>) -> Self {
+        Self { backend }
+    }
+
+    pub async fn get<T>(&self, key: &str) -> Option<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let Some(data) = self.backend.get(key).await else {
+            counter!("cache_misses_total").increment(1);
+            return None;
+        };
+        counter!("cache_hits_total").increment(1);
+
+        let start = Instant::now();
+        let result = match data {
+            CacheData::Text(s) => serde_json::from_str(&s).ok(),
+            CacheData::Bytes(b) => serde_json::from_slice(&b).ok(),
+            CacheData::ByteStream(..) => None,
+        };
+        histogram!("cache_deserialize_duration_seconds").record(start.elapsed().as_secs_f64());
+        result
+    }
+
+    pub async fn set<T>(&self, key: &str, value: &T)
+    where
+        T: Serialize,
+    {
+        let start = Instant::now();
+        let serialized = serde_json::to_string(value);
+        histogram!("cache_serialize_duration_seconds").record(start.elapsed().as_secs_f64());
+
+        if let Ok(serialized) = serialized {
+            let replaced = self.backend.set(key, CacheData::Text(serialized)).await;
+            if !replaced {
+                gauge!("cache_entries").increment(1.0);
             }
         }
+    }
 
-        CacheStats {
-            total_items,
-            valid_items,
-            expired_items,
+    pub async fn set_with_ttl<T>(&self, key: &str, value: &T, ttl: Duration)
+    where
+        T: Serialize,
+    {
+        let start = Instant::now();
+        let serialized = serde_json::to_string(value);
+        histogram!("cache_serialize_duration_seconds").record(start.elapsed().as_secs_f64());
+
+        if let Ok(serialized) = serialized {
+            let replaced = self
+                .backend
+                .set_with_ttl(key, CacheData::Text(serialized), ttl)
+                .await;
+            if !replaced {
+                gauge!("cache_entries").increment(1.0);
+            }
         }
     }
 
-    fn cleanup_expired(cache: &DashMap<String, CachedItem<String>>) {
-        let mut to_remove = Vec::new();
+    /// Cache a large payload as a stream rather than buffering it fully.
+    pub async fn set_stream(
+        &self,
+        key: &str,
+        stream: Pin<Box<dyn Stream<Item = anyhow::Result<Bytes>> + Send>>,
+        content_length: Option<u64>,
+    ) {
+        self.backend
+            .set(key, CacheData::ByteStream(stream, content_length))
+            .await;
+    }
 
-        for entry in cache.iter() {
-            if entry.is_expired() {
-                to_remove.push(entry.key().clone());
-            }
-        }
+    /// Fetch the raw `CacheData`, for callers that want to stream a cached
+    /// payload back out instead of deserializing it as `T`.
+    pub async fn get_raw(&self, key: &str) -> Option<CacheData> {
+        self.backend.get(key).await
+    }
 
-        for key in to_remove {
-            cache.remove(&key);
+    pub async fn remove(&self, key: &str) {
+        if self.backend.remove(key).await {
+            gauge!("cache_entries").decrement(1.0);
         }
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct CacheStats {
-    pub total_items: usize,
-    pub valid_items: usize,
-    pub expired_items: usize,
+    pub async fn contains_key(&self, key: &str) -> bool {
+        self.backend.contains_key(key).await
+    }
 }
 
 impl Default for ResponseCache {
@@ -201,25 +398,37 @@ where
         if let Some(cached_item) = self.cache.get(key) {
             if cached_item.is_expired() {
                 self.cache.remove(key);
+                counter!("cache_misses_total").increment(1);
+                gauge!("cache_entries").decrement(1.0);
                 return None;
             }
+            counter!("cache_hits_total").increment(1);
             return Some(cached_item.data.clone());
         }
+        counter!("cache_misses_total").increment(1);
         None
     }
 
     pub fn set(&self, key: &str, value: T) {
         let cached_item = CachedItem::new(value, Some(self.default_ttl));
-        self.cache.insert(key.to_string(), cached_item);
+        let previous = self.cache.insert(key.to_string(), cached_item);
+        if !previous.is_some_and(|item| !item.is_expired()) {
+            gauge!("cache_entries").increment(1.0);
+        }
     }
 
     pub fn set_with_ttl(&self, key: &str, value: T, ttl: Duration) {
         let cached_item = CachedItem::new(value, Some(ttl));
-        self.cache.insert(key.to_string(), cached_item);
+        let previous = self.cache.insert(key.to_string(), cached_item);
+        if !previous.is_some_and(|item| !item.is_expired()) {
+            gauge!("cache_entries").increment(1.0);
+        }
     }
 
     pub fn remove(&self, key: &str) {
-        self.cache.remove(key);
+        if self.cache.remove(key).is_some() {
+            gauge!("cache_entries").decrement(1.0);
+        }
     }
 
     pub fn clear(&self) {
@@ -0,0 +1,40 @@
+/// The recursive-split + overlap splitting algorithm lives in the `core`
+/// crate's `chunk_spans` module; this crate doesn't depend on `core`, so it
+/// includes the same source file directly rather than reimplementing it —
+/// a fix to the splitting logic only has to be made in one place.
+#[path = "../../src/core/chunk_spans.rs"]
+mod chunk_spans;
+
+use chunk_spans::compute_spans;
+
+/// Recursive-split chunker shared by `HighPerformanceScraper` and
+/// `TextProcessor`: breaks text on the highest-priority separator that
+/// still keeps pieces under the target size (paragraph breaks, then lines,
+/// then sentence boundaries, then whitespace), cutting inside a word only
+/// when a single word exceeds the target size, and carries a character
+/// overlap between consecutive chunks instead of the old `overlap / 10`
+/// word-count approximation. The overlap is counted in characters, so it
+/// can begin part-way through a word.
+pub struct Chunker {
+    target_size: usize,
+    overlap: usize,
+}
+
+impl Chunker {
+    pub fn new(target_size: usize, overlap: usize) -> Self {
+        Self {
+            target_size: target_size.max(1),
+            overlap,
+        }
+    }
+
+    pub fn chunk(&self, text: &str) -> Vec<String> {
+        let measure = |s: &str| s.chars().count();
+        let chunks: Vec<String> = compute_spans(text, self.target_size, self.overlap, &measure)
+            .into_iter()
+            .map(|(start, end)| text[start..end].trim().to_string())
+            .collect();
+
+        chunks.into_iter().filter(|c| !c.is_empty()).collect()
+    }
+}
@@ -1,8 +1,28 @@
+mod cache;
+mod chunker;
+mod processor;
+mod query_enhancer;
+mod ranking;
+mod search_index;
+mod tokenizer;
+mod web_scraper;
+
+use chunker::Chunker;
+use dashmap::DashMap;
+use futures::stream::{self, StreamExt};
 use pyo3::prelude::*;
 use pyo3_asyncio::tokio::future_into_py;
+use rand::Rng;
+use reqwest::StatusCode;
+use scraper::{ElementRef, Html, Selector};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+
+const CONTENT_REMOVE_TAGS: [&str; 5] = ["script", "style", "nav", "footer", "aside"];
+const CONTENT_CANDIDATE_SELECTOR: &str = "article, main, section, div, p";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScrapedContent {
@@ -13,6 +33,14 @@ pub struct ScrapedContent {
     pub timestamp: String,
 }
 
+/// Per-URL outcome from a batch scrape, so callers can see which URLs
+/// failed (and why) instead of the failures being silently dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScrapeOutcome {
+    Success(ScrapedContent),
+    Error { url: String, message: String },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessedText {
     pub original: String,
@@ -22,33 +50,65 @@ pub struct ProcessedText {
     pub summary: String,
 }
 
+/// Shared, clonable scraper state. `HighPerformanceScraper` wraps this in an
+/// `Arc` so the pyo3-exposed methods can clone it into a `'static` future
+/// instead of trying to borrow `&self` across an `await`.
+struct ScraperState {
+    client: reqwest::Client,
+    semaphore: Semaphore,
+    max_concurrent: usize,
+    max_retries: u32,
+    min_host_delay: Duration,
+    host_last_request: DashMap<String, Instant>,
+    /// Per-site CSS selector override for the main-content container, used
+    /// instead of the readability heuristic when callers know the layout.
+    content_selector: std::sync::RwLock<Option<String>>,
+}
+
 #[pyclass]
+#[derive(Clone)]
 pub struct HighPerformanceScraper {
-    max_concurrent: usize,
+    inner: Arc<ScraperState>,
 }
 
 #[pymethods]
 impl HighPerformanceScraper {
     #[new]
     fn new(max_concurrent: usize) -> Self {
-        Self { max_concurrent }
+        let client = reqwest::Client::builder()
+            .cookie_store(true)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            inner: Arc::new(ScraperState {
+                client,
+                semaphore: Semaphore::new(max_concurrent),
+                max_concurrent,
+                max_retries: 3,
+                min_host_delay: Duration::from_millis(250),
+                host_last_request: DashMap::new(),
+                content_selector: std::sync::RwLock::new(None),
+            }),
+        }
     }
 
     fn scrape_urls(&self, py: Python, urls: Vec<String>) -> PyResult<PyObject> {
+        let this = self.clone();
         future_into_py(py, async move {
-            let mut results = Vec::new();
-            
-            for url in urls {
-                match self.scrape_single_url(&url).await {
-                    Ok(content) => results.push(content),
-                    Err(e) => eprintln!("Failed to scrape {}: {}", url, e),
-                }
-            }
-            
+            let results = this.scrape_urls_impl(urls).await;
             Ok(results)
         })
     }
 
+    /// Override the main-content CSS selector used for every subsequent
+    /// scrape, for sites where the readability heuristic picks the wrong
+    /// container.
+    fn set_content_selector(&self, selector: Option<String>) {
+        *self.inner.content_selector.write().unwrap() = selector;
+    }
+
     fn process_text_batch(&self, py: Python, texts: Vec<String>) -> PyResult<PyObject> {
         future_into_py(py, async move {
             let mut results = Vec::new();
@@ -78,24 +138,117 @@ impl HighPerformanceScraper {
 }
 
 impl HighPerformanceScraper {
-    async fn scrape_single_url(&self, url: &str) -> Result<ScrapedContent, Box<dyn std::error::Error>> {
-        let client = reqwest::Client::new();
-        let response = client.get(url).send().await?;
+    /// Fetch every URL concurrently (bounded by `max_concurrent`), retrying
+    /// transient failures with exponential backoff and honoring a per-host
+    /// minimum delay so a single domain isn't hammered.
+    async fn scrape_urls_impl(&self, urls: Vec<String>) -> Vec<ScrapeOutcome> {
+        let inner = self.inner.clone();
+
+        stream::iter(urls)
+            .map(|url| {
+                let inner = inner.clone();
+                async move {
+                    let _permit = inner.semaphore.acquire().await;
+                    match Self::scrape_with_retries(&inner, &url).await {
+                        Ok(content) => ScrapeOutcome::Success(content),
+                        Err(e) => ScrapeOutcome::Error {
+                            url,
+                            message: e.to_string(),
+                        },
+                    }
+                }
+            })
+            .buffer_unordered(inner.max_concurrent.max(1))
+            .collect()
+            .await
+    }
+
+    async fn scrape_with_retries(
+        inner: &Arc<ScraperState>,
+        url: &str,
+    ) -> Result<ScrapedContent, Box<dyn std::error::Error>> {
+        let mut attempt = 0;
+
+        loop {
+            Self::wait_for_host_slot(inner, url).await;
+
+            match Self::scrape_single_url(inner, url).await {
+                Ok(content) => return Ok(content),
+                Err(e) if attempt < inner.max_retries && Self::is_transient(&e) => {
+                    let backoff = Self::backoff_with_jitter(attempt);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn is_transient(err: &Box<dyn std::error::Error>) -> bool {
+        if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
+            if let Some(status) = reqwest_err.status() {
+                return status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+            }
+            return reqwest_err.is_timeout() || reqwest_err.is_connect();
+        }
+        false
+    }
+
+    fn backoff_with_jitter(attempt: u32) -> Duration {
+        let base_ms = 200u64.saturating_mul(1u64 << attempt.min(6));
+        let jitter_ms = rand::thread_rng().gen_range(0..=base_ms / 2 + 1);
+        Duration::from_millis(base_ms + jitter_ms)
+    }
+
+    /// Block until at least `min_host_delay` has passed since the last
+    /// request to this URL's host.
+    async fn wait_for_host_slot(inner: &Arc<ScraperState>, url: &str) {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_default();
+
+        // Reserve this request's send time under the entry lock, so
+        // concurrent requests to one host are spaced apart instead of all
+        // reading the same previous timestamp and firing together.
+        let now = Instant::now();
+        let scheduled = {
+            let mut last = inner
+                .host_last_request
+                .entry(host)
+                .or_insert_with(|| now.checked_sub(inner.min_host_delay).unwrap_or(now));
+            let slot = (*last + inner.min_host_delay).max(now);
+            *last = slot;
+            slot
+        };
+
+        if scheduled > now {
+            tokio::time::sleep_until(scheduled.into()).await;
+        }
+    }
+
+    async fn scrape_single_url(
+        inner: &Arc<ScraperState>,
+        url: &str,
+    ) -> Result<ScrapedContent, Box<dyn std::error::Error>> {
+        let response = inner.client.get(url).send().await?;
+        let response = response.error_for_status()?;
         let html = response.text().await?;
-        
-        // Simple HTML parsing - extract title and content
-        let title = self.extract_title(&html);
-        let content = self.extract_content(&html);
-        
-        let mut metadata = HashMap::new();
+        let document = Html::parse_document(&html);
+
+        let title = Self::extract_title(&document);
+        let selector_override = inner.content_selector.read().unwrap().clone();
+        let content = Self::extract_content(&document, selector_override.as_deref());
+
+        let mut metadata = Self::extract_meta_tags(&document);
         metadata.insert("content_type".to_string(), "text/html".to_string());
         metadata.insert("url".to_string(), url.to_string());
-        
+
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        
+
         Ok(ScrapedContent {
             url: url.to_string(),
             title,
@@ -105,30 +258,95 @@ impl HighPerformanceScraper {
         })
     }
 
-    fn extract_title(&self, html: &str) -> String {
-        if let Some(start) = html.find("<title>") {
-            if let Some(end) = html[start + 7..].find("</title>") {
-                return html[start + 7..start + 7 + end].to_string();
+    fn extract_title(document: &Html) -> String {
+        let selector = Selector::parse("title").unwrap();
+        document
+            .select(&selector)
+            .next()
+            .map(|el| Self::clean_whitespace(&el.text().collect::<String>()))
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| "No title".to_string())
+    }
+
+    /// Readability-style main-content extraction: parse the DOM, skip
+    /// candidate block elements that are (or sit inside) script/style/nav/
+    /// footer/aside elements, and score the remaining candidates by
+    /// text-length-to-link-density ratio. The chosen container's text, and
+    /// its score, still include any such elements nested inside it.
+    fn extract_content(document: &Html, selector_override: Option<&str>) -> String {
+        if let Some(selector_str) = selector_override {
+            if let Ok(selector) = Selector::parse(selector_str) {
+                if let Some(element) = document.select(&selector).next() {
+                    return Self::clean_whitespace(&element.text().collect::<String>());
+                }
             }
         }
-        "No title".to_string()
+
+        let candidate_selector = Selector::parse(CONTENT_CANDIDATE_SELECTOR).unwrap();
+
+        let best = document
+            .select(&candidate_selector)
+            .filter(|el| !Self::is_in_removed_subtree(el))
+            .map(|el| (Self::content_score(&el), el))
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        match best {
+            Some((_, el)) => Self::clean_whitespace(&el.text().collect::<String>()),
+            None => Self::clean_whitespace(&document.root_element().text().collect::<String>()),
+        }
     }
 
-    fn extract_content(&self, html: &str) -> String {
-        // Simple content extraction - remove HTML tags
-        let mut content = html.to_string();
-        
-        // Remove script and style tags
-        content = regex::Regex::new(r"<script[^>]*>.*?</script>").unwrap().replace_all(&content, "").to_string();
-        content = regex::Regex::new(r"<style[^>]*>.*?</style>").unwrap().replace_all(&content, "").to_string();
-        
-        // Remove HTML tags
-        content = regex::Regex::new(r"<[^>]+>").unwrap().replace_all(&content, " ").to_string();
-        
-        // Clean up whitespace
-        content = regex::Regex::new(r"\s+").unwrap().replace_all(&content, " ").to_string();
-        
-        content.trim().to_string()
+    fn is_in_removed_subtree(el: &ElementRef) -> bool {
+        CONTENT_REMOVE_TAGS.contains(&el.value().name())
+            || el.ancestors().any(|node| {
+                node.value()
+                    .as_element()
+                    .map(|e| CONTENT_REMOVE_TAGS.contains(&e.name()))
+                    .unwrap_or(false)
+            })
+    }
+
+    /// Text-length-to-link-density ratio: favors large blocks of prose over
+    /// nav-like clusters of short, link-heavy text.
+    fn content_score(el: &ElementRef) -> f64 {
+        let text_len: usize = el.text().map(|t| t.len()).sum();
+        if text_len == 0 {
+            return 0.0;
+        }
+
+        let link_selector = Selector::parse("a").unwrap();
+        let link_len: usize = el
+            .select(&link_selector)
+            .flat_map(|a| a.text())
+            .map(|t| t.len())
+            .sum();
+
+        let link_density = link_len as f64 / text_len as f64;
+        text_len as f64 * (1.0 - link_density).max(0.0)
+    }
+
+    fn extract_meta_tags(document: &Html) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        let meta_selector = Selector::parse("meta").unwrap();
+
+        for meta in document.select(&meta_selector) {
+            let key = meta
+                .value()
+                .attr("name")
+                .or_else(|| meta.value().attr("property"));
+            if let (Some(key), Some(content)) = (key, meta.value().attr("content")) {
+                metadata.insert(key.to_string(), content.to_string());
+            }
+        }
+
+        metadata
+    }
+
+    fn clean_whitespace(text: &str) -> String {
+        regex::Regex::new(r"\s+")
+            .unwrap()
+            .replace_all(text.trim(), " ")
+            .to_string()
     }
 
     async fn process_single_text(&self, text: &str) -> ProcessedText {
@@ -168,49 +386,7 @@ impl HighPerformanceScraper {
     }
 
     async fn chunk_text_impl(&self, text: &str, chunk_size: usize, overlap: usize) -> Vec<String> {
-        let sentences: Vec<&str> = regex::Regex::new(r"[.!?]+").unwrap()
-            .split(text)
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .collect();
-        
-        let mut chunks = Vec::new();
-        let mut current_chunk = String::new();
-        let mut current_size = 0;
-        
-        for sentence in sentences {
-            let sentence_size = sentence.len();
-            
-            if current_size + sentence_size > chunk_size && !current_chunk.is_empty() {
-                chunks.push(current_chunk.trim().to_string());
-                
-                // Start new chunk with overlap
-                if overlap > 0 {
-                    let words: Vec<&str> = current_chunk.split_whitespace().collect();
-                    let overlap_words = (overlap / 10).min(words.len());
-                    if overlap_words > 0 {
-                        current_chunk = words[words.len() - overlap_words..].join(" ") + " ";
-                        current_size = current_chunk.len();
-                    } else {
-                        current_chunk = String::new();
-                        current_size = 0;
-                    }
-                } else {
-                    current_chunk = String::new();
-                    current_size = 0;
-                }
-            }
-            
-            current_chunk.push_str(sentence);
-            current_chunk.push_str(". ");
-            current_size += sentence_size + 2;
-        }
-        
-        if !current_chunk.trim().is_empty() {
-            chunks.push(current_chunk.trim().to_string());
-        }
-        
-        chunks
+        Chunker::new(chunk_size, overlap).chunk(text)
     }
 
     async fn extract_keywords_impl(&self, text: &str, max_keywords: usize) -> Vec<String> {
@@ -1,3 +1,8 @@
+use crate::chunker::Chunker;
+use crate::query_enhancer::QueryEnhancer;
+use crate::ranking::{self, ChunkMetrics, Criterion};
+use crate::search_index::SearchIndex;
+use crate::tokenizer::Tokenizer;
 use anyhow::Result;
 use rayon::prelude::*;
 use regex::Regex;
@@ -7,7 +12,7 @@ use tokio::sync::Mutex;
 
 pub struct TextProcessor {
     stop_words: Arc<Vec<String>>,
-    word_regex: Regex,
+    tokenizer: Tokenizer,
     sentence_regex: Regex,
 }
 
@@ -28,11 +33,23 @@ impl TextProcessor {
 
         Self {
             stop_words: Arc::new(stop_words),
-            word_regex: Regex::new(r"\b[a-zA-Z]+\b").unwrap(),
+            tokenizer: Tokenizer::new(),
             sentence_regex: Regex::new(r"[.!?]+").unwrap(),
         }
     }
 
+    /// Tokenize `text` and drop tokens that are too short or are stop
+    /// words, honoring script-aware rules (see `Tokenizer`).
+    fn meaningful_words(&self, text: &str) -> Vec<String> {
+        self.tokenizer
+            .tokenize(text)
+            .into_iter()
+            .filter(|word| {
+                self.tokenizer.is_meaningful(word) && !self.tokenizer.is_stop_word(word, &self.stop_words)
+            })
+            .collect()
+    }
+
     pub async fn process_text(&self, text: &str) -> Result<ProcessedText> {
         let cleaned = self.clean_text_sync(text);
         let chunks = self.chunk_text(&cleaned, 1000, 200).await?;
@@ -85,113 +102,16 @@ impl TextProcessor {
     }
 
     pub async fn chunk_text(&self, text: &str, chunk_size: usize, overlap: usize) -> Result<Vec<String>> {
-        let sentences: Vec<&str> = self.sentence_regex
-            .split(text)
-            .filter(|s| !s.trim().is_empty())
-            .collect();
-
-        let mut chunks = Vec::new();
-        let mut current_chunk = String::new();
-        let mut current_size = 0;
-
-        for sentence in sentences {
-            let sentence = sentence.trim();
-            if sentence.is_empty() {
-                continue;
-            }
-
-            let sentence_size = sentence.len();
-            
-            if current_size + sentence_size > chunk_size && !current_chunk.is_empty() {
-                chunks.push(current_chunk.trim().to_string());
-                
-                // Start new chunk with overlap
-                if overlap > 0 {
-                    let words: Vec<&str> = current_chunk.split_whitespace().collect();
-                    let overlap_words = (overlap / 10).min(words.len()); // Rough estimate
-                    if overlap_words > 0 {
-                        current_chunk = words[words.len() - overlap_words..].join(" ") + " ";
-                        current_size = current_chunk.len();
-                    } else {
-                        current_chunk = String::new();
-                        current_size = 0;
-                    }
-                } else {
-                    current_chunk = String::new();
-                    current_size = 0;
-                }
-            }
-
-            current_chunk.push_str(sentence);
-            current_chunk.push_str(". ");
-            current_size += sentence_size + 2;
-        }
-
-        if !current_chunk.trim().is_empty() {
-            chunks.push(current_chunk.trim().to_string());
-        }
-
-        Ok(chunks)
+        Ok(Chunker::new(chunk_size, overlap).chunk(text))
     }
 
     pub fn chunk_text_sync(&self, text: &str, chunk_size: usize, overlap: usize) -> Vec<String> {
         // Synchronous version for use in parallel processing
-        let sentences: Vec<&str> = self.sentence_regex
-            .split(text)
-            .filter(|s| !s.trim().is_empty())
-            .collect();
-
-        let mut chunks = Vec::new();
-        let mut current_chunk = String::new();
-        let mut current_size = 0;
-
-        for sentence in sentences {
-            let sentence = sentence.trim();
-            if sentence.is_empty() {
-                continue;
-            }
-
-            let sentence_size = sentence.len();
-            
-            if current_size + sentence_size > chunk_size && !current_chunk.is_empty() {
-                chunks.push(current_chunk.trim().to_string());
-                
-                if overlap > 0 {
-                    let words: Vec<&str> = current_chunk.split_whitespace().collect();
-                    let overlap_words = (overlap / 10).min(words.len());
-                    if overlap_words > 0 {
-                        current_chunk = words[words.len() - overlap_words..].join(" ") + " ";
-                        current_size = current_chunk.len();
-                    } else {
-                        current_chunk = String::new();
-                        current_size = 0;
-                    }
-                } else {
-                    current_chunk = String::new();
-                    current_size = 0;
-                }
-            }
-
-            current_chunk.push_str(sentence);
-            current_chunk.push_str(". ");
-            current_size += sentence_size + 2;
-        }
-
-        if !current_chunk.trim().is_empty() {
-            chunks.push(current_chunk.trim().to_string());
-        }
-
-        chunks
+        Chunker::new(chunk_size, overlap).chunk(text)
     }
 
     pub async fn extract_keywords(&self, text: &str, max_keywords: usize) -> Result<Vec<String>> {
-        let words: Vec<String> = self.word_regex
-            .find_iter(text)
-            .map(|m| m.as_str().to_lowercase())
-            .filter(|word| {
-                word.len() > 2 && !self.stop_words.contains(word)
-            })
-            .collect();
+        let words = self.meaningful_words(text);
 
         let mut word_freq: HashMap<String, usize> = HashMap::new();
         for word in words {
@@ -208,14 +128,79 @@ impl TextProcessor {
             .collect())
     }
 
+    /// Extract multi-word keyphrases (up to `max_n` tokens long) instead of
+    /// only unigrams, ranking candidates by a PMI-style cohesion score —
+    /// an n-gram's frequency squared divided by the geometric mean of its
+    /// component unigrams' frequencies, squared — so collocations like
+    /// "machine learning" outrank coincidental word pairs. That cohesion
+    /// score is bounded to `(0, 1]` (an n-gram's frequency can never exceed
+    /// the geometric mean of its component words' frequencies, since it
+    /// can't exceed their minimum), and — unlike dividing by the raw
+    /// component product — stays frequency-monotonic for `n > 2`: a fully
+    /// cohesive trigram scores `1` regardless of how often it occurs,
+    /// instead of shrinking as `1/freq`. Unigrams are scored on the same
+    /// `(0, 1]` scale — relative frequency (`freq / total_words`) rather
+    /// than raw count — otherwise any unigram repeated a handful of times
+    /// would outscore every phrase regardless of how cohesive it was.
+    pub fn extract_keyphrases(&self, text: &str, max_n: usize, max_phrases: usize) -> Vec<(String, f64)> {
+        let words = self.meaningful_words(text);
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let total_words = words.len() as f64;
+        let unigram_freq = Self::ngram_frequencies(&words, 1);
+
+        let mut scored: Vec<(String, f64)> = Vec::new();
+        for n in 1..=max_n.max(1) {
+            let freqs = if n == 1 {
+                unigram_freq.clone()
+            } else {
+                Self::ngram_frequencies(&words, n)
+            };
+
+            for (phrase, freq) in freqs {
+                let freq = freq as f64;
+                let score = if n == 1 {
+                    freq / total_words
+                } else {
+                    let component_product: f64 = phrase
+                        .split(' ')
+                        .map(|w| *unigram_freq.get(w).unwrap_or(&1) as f64)
+                        .product();
+                    // Divide by the geometric mean of the components,
+                    // squared, rather than the raw product, so the bound
+                    // stays tight (and the score frequency-monotonic) for
+                    // n > 2 instead of just n == 2.
+                    let geomean_squared = component_product.powf(2.0 / n as f64);
+                    // Clamp away `powf` rounding (e.g. 8^(2/3) comes out
+                    // just under 4), which would otherwise push a fully
+                    // cohesive n-gram a hair above the `1` bound.
+                    ((freq * freq) / geomean_squared.max(1.0)).min(1.0)
+                };
+                scored.push((phrase, score));
+            }
+        }
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scored.truncate(max_phrases);
+        scored
+    }
+
+    /// Count contiguous n-grams of length `n` over `words`.
+    fn ngram_frequencies(words: &[String], n: usize) -> HashMap<String, usize> {
+        let mut freq = HashMap::new();
+        if n == 0 || words.len() < n {
+            return freq;
+        }
+        for window in words.windows(n) {
+            *freq.entry(window.join(" ")).or_insert(0) += 1;
+        }
+        freq
+    }
+
     pub fn extract_keywords_sync(&self, text: &str, max_keywords: usize) -> Vec<String> {
-        let words: Vec<String> = self.word_regex
-            .find_iter(text)
-            .map(|m| m.as_str().to_lowercase())
-            .filter(|word| {
-                word.len() > 2 && !self.stop_words.contains(word)
-            })
-            .collect();
+        let words = self.meaningful_words(text);
 
         let mut word_freq: HashMap<String, usize> = HashMap::new();
         for word in words {
@@ -312,13 +297,7 @@ impl TextProcessor {
     }
 
     fn calculate_word_frequency(&self, text: &str) -> HashMap<String, f64> {
-        let words: Vec<String> = self.word_regex
-            .find_iter(text)
-            .map(|m| m.as_str().to_lowercase())
-            .filter(|word| {
-                word.len() > 2 && !self.stop_words.contains(word)
-            })
-            .collect();
+        let words = self.meaningful_words(text);
 
         let total_words = words.len() as f64;
         let mut word_freq: HashMap<String, usize> = HashMap::new();
@@ -334,13 +313,7 @@ impl TextProcessor {
     }
 
     fn calculate_sentence_score(&self, sentence: &str, word_freq: &HashMap<String, f64>) -> f64 {
-        let words: Vec<String> = self.word_regex
-            .find_iter(sentence)
-            .map(|m| m.as_str().to_lowercase())
-            .filter(|word| {
-                word.len() > 2 && !self.stop_words.contains(word)
-            })
-            .collect();
+        let words = self.meaningful_words(sentence);
 
         words
             .iter()
@@ -348,23 +321,63 @@ impl TextProcessor {
             .sum()
     }
 
-    pub fn calculate_similarity_sync(&self, text1: &str, text2: &str) -> f64 {
-        let words1: std::collections::HashSet<String> = self.word_regex
-            .find_iter(text1)
-            .map(|m| m.as_str().to_lowercase())
-            .filter(|word| {
-                word.len() > 2 && !self.stop_words.contains(word)
-            })
-            .collect();
+    /// Build a typo-tolerant `SearchIndex` over a corpus of processed texts,
+    /// so callers can query it instead of only comparing documents pairwise.
+    pub fn build_search_index(&self, texts: &[ProcessedText]) -> SearchIndex {
+        let mut index = SearchIndex::new();
+        index.ingest(texts);
+        index
+    }
 
-        let words2: std::collections::HashSet<String> = self.word_regex
-            .find_iter(text2)
-            .map(|m| m.as_str().to_lowercase())
-            .filter(|word| {
-                word.len() > 2 && !self.stop_words.contains(word)
-            })
+    /// Build a `SearchIndex` over `texts` and run a boolean, prefix-aware
+    /// query against it in one call.
+    pub fn search_query(&self, texts: &[ProcessedText], query: &str) -> Vec<(usize, f64)> {
+        self.build_search_index(texts).search_query(query)
+    }
+
+    /// Like `search_query`, but first rewrites the query through `enhancer`
+    /// (synonyms plus automatic split/concatenation) before evaluating it,
+    /// so recall improves without the caller needing to guess spacing.
+    pub fn search_query_expanded(
+        &self,
+        texts: &[ProcessedText],
+        query: &str,
+        enhancer: &QueryEnhancer,
+    ) -> Vec<(usize, f64)> {
+        let index = self.build_search_index(texts);
+        let words: Vec<String> = index
+            .tokenize(query)
+            .into_iter()
+            .filter(|w| w.len() > 2 && !self.stop_words.contains(w))
             .collect();
 
+        let expanded = enhancer.expand(&index, &words);
+        index
+            .evaluate(&expanded.operation)
+            .into_iter()
+            .map(|(chunk_id, _words_matched, freq)| (chunk_id, freq as f64))
+            .collect()
+    }
+
+    /// Build a `SearchIndex` over `texts` and rank matching chunks through
+    /// `criteria` (typo/words/proximity/exactness/frequency, in whatever
+    /// order the caller wants), rather than only sorting by raw frequency.
+    pub fn rank_query(
+        &self,
+        texts: &[ProcessedText],
+        query: &str,
+        criteria: &[Criterion],
+    ) -> Vec<ChunkMetrics> {
+        let index = self.build_search_index(texts);
+        ranking::rank_query(&index, query, criteria)
+    }
+
+    pub fn calculate_similarity_sync(&self, text1: &str, text2: &str) -> f64 {
+        let words1: std::collections::HashSet<String> =
+            self.meaningful_words(text1).into_iter().collect();
+        let words2: std::collections::HashSet<String> =
+            self.meaningful_words(text2).into_iter().collect();
+
         let intersection = words1.intersection(&words2).count();
         let union = words1.union(&words2).count();
 
@@ -384,3 +397,55 @@ pub struct ProcessedText {
     pub keywords: Vec<String>,
     pub summary: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cohesive_phrase_outranks_a_frequent_unigram() {
+        let processor = TextProcessor::new();
+        // "machine learning" always occurs as a pair (perfectly cohesive),
+        // while "data" is merely frequent on its own.
+        let text = "machine learning machine learning machine learning \
+                     data data data data data analysis";
+
+        let phrases = processor.extract_keyphrases(text, 2, 10);
+        let rank_of = |phrase: &str| phrases.iter().position(|(p, _)| p == phrase).unwrap();
+
+        assert!(
+            rank_of("machine learning") < rank_of("data"),
+            "expected \"machine learning\" to outrank \"data\", got {:?}",
+            phrases
+        );
+    }
+
+    #[test]
+    fn cohesion_score_is_frequency_monotonic_for_trigrams() {
+        let processor = TextProcessor::new();
+        // "machine learning model" always occurs as a triple (perfectly
+        // cohesive) in both texts; the only difference is how often.
+        let rare_text = "machine learning model machine learning model data analysis";
+        let frequent_text = "machine learning model ".repeat(50) + "data analysis";
+
+        let rare_score = processor
+            .extract_keyphrases(rare_text, 3, 10)
+            .into_iter()
+            .find(|(p, _)| p == "machine learning model")
+            .map(|(_, score)| score)
+            .unwrap();
+        let frequent_score = processor
+            .extract_keyphrases(&frequent_text, 3, 10)
+            .into_iter()
+            .find(|(p, _)| p == "machine learning model")
+            .map(|(_, score)| score)
+            .unwrap();
+
+        assert!(
+            frequent_score >= rare_score,
+            "expected a more frequent, equally cohesive trigram to score no lower \
+             ({frequent_score} < {rare_score})"
+        );
+        assert!((0.0..=1.0).contains(&frequent_score));
+    }
+}
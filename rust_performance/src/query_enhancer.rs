@@ -0,0 +1,168 @@
+use crate::search_index::{Operation, Query, SearchIndex};
+use std::collections::HashMap;
+
+/// Minimum character length a query word must have before automatic
+/// split-into-two-words rewriting is attempted. Shorter words produce too
+/// many spurious splits to be worth the extra branches.
+const MIN_SPLIT_LEN: usize = 4;
+
+/// An `Operation` tree plus a record of which original query word position
+/// each expanded leaf term satisfies, so downstream ranking can still
+/// attribute a match back to the word the user actually typed.
+#[derive(Debug, Clone)]
+pub struct ExpandedQuery {
+    pub operation: Operation,
+    pub term_positions: HashMap<String, usize>,
+}
+
+/// Rewrites query tokens before index lookup: synonym substitution plus
+/// automatic split ("powershell" -> "power shell") and adjacent-pair
+/// concatenation ("data base" -> "database"), each threaded into the query
+/// tree as an additional `Or` branch so recall improves without the caller
+/// needing to guess spacing or register every synonym form.
+#[derive(Default)]
+pub struct QueryEnhancer {
+    /// word -> alternative phrases (each phrase itself a sequence of words,
+    /// so multi-word synonyms like "new york" for "nyc" are supported).
+    synonyms: HashMap<String, Vec<Vec<String>>>,
+}
+
+impl QueryEnhancer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `phrase` as a synonym for `term`. Safe to call repeatedly
+    /// with the same term to add further alternatives.
+    pub fn register_synonym(&mut self, term: &str, phrase: Vec<String>) {
+        self.synonyms
+            .entry(term.to_string())
+            .or_insert_with(Vec::new)
+            .push(phrase);
+    }
+
+    /// Expand already-tokenized, stop-word-filtered `words` into a query
+    /// tree against `index`. The last word is treated as a prefix match, as
+    /// in `SearchIndex::parse_query`.
+    pub fn expand(&self, index: &SearchIndex, words: &[String]) -> ExpandedQuery {
+        let last_index = words.len().saturating_sub(1);
+        let mut term_positions = HashMap::new();
+
+        let per_position: Vec<Operation> = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| self.expand_word(index, word, i == last_index, i, &mut term_positions))
+            .collect();
+
+        let mut variants = vec![Self::and_of(per_position.clone())];
+
+        for i in 0..words.len().saturating_sub(1) {
+            let concat = format!("{}{}", words[i], words[i + 1]);
+            if index.contains_term(&concat) {
+                let prefix = i + 1 == last_index;
+                let mut variant = per_position.clone();
+                variant.splice(i..=i + 1, [Self::leaf(concat.clone(), prefix)]);
+                term_positions.entry(concat).or_insert(i);
+                variants.push(Self::and_of(variant));
+            }
+        }
+
+        let operation = if variants.len() == 1 {
+            variants.into_iter().next().unwrap()
+        } else {
+            Operation::Or(variants)
+        };
+
+        ExpandedQuery {
+            operation,
+            term_positions,
+        }
+    }
+
+    fn expand_word(
+        &self,
+        index: &SearchIndex,
+        word: &str,
+        prefix: bool,
+        position: usize,
+        term_positions: &mut HashMap<String, usize>,
+    ) -> Operation {
+        term_positions.entry(word.to_string()).or_insert(position);
+        let mut branches = vec![Self::leaf(word.to_string(), prefix)];
+
+        for phrase in self.synonyms.get(word).into_iter().flatten() {
+            for synonym in phrase {
+                term_positions.entry(synonym.clone()).or_insert(position);
+            }
+            branches.push(if phrase.len() == 1 {
+                Self::leaf(phrase[0].clone(), prefix)
+            } else {
+                Operation::And(phrase.iter().map(|w| Self::leaf(w.clone(), false)).collect())
+            });
+        }
+
+        if let Some((left, right)) = Self::best_split(index, word) {
+            term_positions.entry(left.clone()).or_insert(position);
+            term_positions.entry(right.clone()).or_insert(position);
+            branches.push(Operation::And(vec![
+                Self::leaf(left, false),
+                Self::leaf(right, prefix),
+            ]));
+        }
+
+        Self::or_of(branches)
+    }
+
+    /// Try splitting `word` at every internal position and keep the split
+    /// whose two halves both exist in `index`'s vocabulary with the highest
+    /// combined term frequency.
+    fn best_split(index: &SearchIndex, word: &str) -> Option<(String, String)> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() < MIN_SPLIT_LEN {
+            return None;
+        }
+
+        let mut best: Option<(String, String, usize)> = None;
+        for split_at in 1..chars.len() {
+            let left: String = chars[..split_at].iter().collect();
+            let right: String = chars[split_at..].iter().collect();
+
+            let left_freq = index.term_frequency(&left);
+            let right_freq = index.term_frequency(&right);
+            if left_freq == 0 || right_freq == 0 {
+                continue;
+            }
+
+            let combined = left_freq + right_freq;
+            if best.as_ref().map_or(true, |(_, _, best_combined)| combined > *best_combined) {
+                best = Some((left, right, combined));
+            }
+        }
+
+        best.map(|(left, right, _)| (left, right))
+    }
+
+    fn leaf(term: String, prefix: bool) -> Operation {
+        Operation::Query(Query {
+            tolerant: !prefix,
+            prefix,
+            term,
+        })
+    }
+
+    fn and_of(mut ops: Vec<Operation>) -> Operation {
+        if ops.len() == 1 {
+            ops.pop().unwrap()
+        } else {
+            Operation::And(ops)
+        }
+    }
+
+    fn or_of(mut ops: Vec<Operation>) -> Operation {
+        if ops.len() == 1 {
+            ops.pop().unwrap()
+        } else {
+            Operation::Or(ops)
+        }
+    }
+}
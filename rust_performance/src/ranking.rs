@@ -0,0 +1,172 @@
+use crate::search_index::SearchIndex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Successive tie-breaking stages applied to search hits, bucket-sort style:
+/// chunks are ordered by the first criterion, then ties within a group are
+/// broken by the next, and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    /// Fewest total edit-distance across matched query words.
+    Typo,
+    /// Most distinct query words matched.
+    Words,
+    /// Smallest sum of gaps between matched word positions in the chunk.
+    Proximity,
+    /// Most matches that are whole-word exact rather than tolerant/prefix.
+    Exactness,
+    /// Highest total term frequency, as a final fallback.
+    Frequency,
+}
+
+impl Criterion {
+    /// The repo's default ranking pipeline.
+    pub fn default_pipeline() -> Vec<Criterion> {
+        vec![
+            Criterion::Typo,
+            Criterion::Words,
+            Criterion::Proximity,
+            Criterion::Exactness,
+            Criterion::Frequency,
+        ]
+    }
+
+    fn compare(&self, a: &ChunkMetrics, b: &ChunkMetrics) -> Ordering {
+        match self {
+            Criterion::Typo => a.typo.cmp(&b.typo),
+            Criterion::Words => b.words_matched.cmp(&a.words_matched),
+            Criterion::Proximity => a.proximity.cmp(&b.proximity),
+            Criterion::Exactness => b.exact_matches.cmp(&a.exact_matches),
+            Criterion::Frequency => b.frequency.cmp(&a.frequency),
+        }
+    }
+}
+
+/// Per-chunk ranking signals gathered while matching a conjunctive query
+/// against a `SearchIndex`.
+#[derive(Debug, Clone)]
+pub struct ChunkMetrics {
+    pub chunk_id: usize,
+    pub typo: usize,
+    pub words_matched: usize,
+    pub proximity: usize,
+    pub exact_matches: usize,
+    pub frequency: usize,
+}
+
+/// Classic full-matrix Levenshtein distance. Ranking needs the exact
+/// distance rather than the yes/no-within-a-threshold answer the automaton
+/// in `search_index` gives, so it's computed directly here.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, &ca) in a.iter().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            let deletion = row[j] + 1;
+            let insertion = row[j + 1] + 1;
+            let substitution = prev + cost;
+            prev = row[j + 1];
+            row[j + 1] = deletion.min(insertion).min(substitution);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Match a single query word (fuzzy or prefix, per `SearchIndex`'s own
+/// rules) against `index`, returning per chunk the closest matching term's
+/// edit distance, whether that match was whole-word exact, the matched
+/// positions within the chunk, and the term's frequency there.
+fn word_matches(
+    index: &SearchIndex,
+    word: &str,
+    prefix: bool,
+) -> HashMap<usize, (usize, bool, Vec<usize>, usize)> {
+    let mut best: HashMap<usize, (usize, bool, Vec<usize>, usize)> = HashMap::new();
+
+    for term in index.candidate_terms(word, prefix) {
+        let distance = edit_distance(word, term);
+        let exact = term == word;
+
+        if let Some(postings) = index.postings(term) {
+            for (chunk_id, positions) in postings {
+                let is_better = match best.get(chunk_id) {
+                    Some((best_distance, _, _, _)) => distance < *best_distance,
+                    None => true,
+                };
+                if is_better {
+                    best.insert(*chunk_id, (distance, exact, positions.clone(), positions.len()));
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// Rank the chunks that match every word of `query` (conjunctively, with
+/// the last word treated as a prefix) through the given `criteria` pipeline.
+pub fn rank_query(index: &SearchIndex, query: &str, criteria: &[Criterion]) -> Vec<ChunkMetrics> {
+    let words = index.tokenize(query);
+    if words.is_empty() {
+        return Vec::new();
+    }
+    let last_index = words.len() - 1;
+
+    let per_word: Vec<HashMap<usize, (usize, bool, Vec<usize>, usize)>> = words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| word_matches(index, word, i == last_index))
+        .collect();
+
+    let mut candidate_chunks: Vec<usize> = per_word[0].keys().copied().collect();
+    for matches in &per_word[1..] {
+        candidate_chunks.retain(|chunk_id| matches.contains_key(chunk_id));
+    }
+
+    let mut metrics: Vec<ChunkMetrics> = candidate_chunks
+        .into_iter()
+        .map(|chunk_id| {
+            let mut typo = 0;
+            let mut exact_matches = 0;
+            let mut frequency = 0;
+            let mut positions: Vec<usize> = Vec::new();
+
+            for matches in &per_word {
+                let (distance, exact, word_positions, freq) = &matches[&chunk_id];
+                typo += distance;
+                if *exact {
+                    exact_matches += 1;
+                }
+                frequency += freq;
+                positions.extend(word_positions.iter().copied());
+            }
+
+            positions.sort_unstable();
+            let proximity = positions.windows(2).map(|pair| pair[1] - pair[0]).sum();
+
+            ChunkMetrics {
+                chunk_id,
+                typo,
+                words_matched: words.len(),
+                proximity,
+                exact_matches,
+                frequency,
+            }
+        })
+        .collect();
+
+    // Stable-sort by each criterion in reverse priority order, so the final
+    // pass (the highest-priority criterion) decides the primary order while
+    // every earlier pass has already settled ties within it.
+    for criterion in criteria.iter().rev() {
+        metrics.sort_by(|a, b| criterion.compare(a, b));
+    }
+
+    metrics
+}
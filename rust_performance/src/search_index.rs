@@ -0,0 +1,357 @@
+use crate::processor::ProcessedText;
+use regex::Regex;
+use std::collections::HashMap;
+
+const STOP_WORDS: [&str; 25] = [
+    "the", "a", "an", "and", "or", "but", "in", "on", "at", "to", "for", "of", "with", "by", "is",
+    "are", "was", "were", "be", "been", "being", "this", "that", "these", "those",
+];
+
+/// A single query leaf: a token plus how it should be matched.
+#[derive(Debug, Clone)]
+pub struct Query {
+    pub term: String,
+    pub prefix: bool,
+    pub tolerant: bool,
+}
+
+/// Boolean tree of query operations, evaluated against the inverted index.
+#[derive(Debug, Clone)]
+pub enum Operation {
+    And(Vec<Operation>),
+    Or(Vec<Operation>),
+    Query(Query),
+}
+
+/// Max edit distance allowed for a query word, scaled by its length.
+fn max_distance_for(word: &str) -> usize {
+    match word.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Classic row-based Levenshtein NFA->DFA: each step folds the previous
+/// edit-distance row into the next one across the full `0..=|term|` range,
+/// pruning as soon as every cell in the row exceeds `max_distance`. This is
+/// O(|term| * |candidate|), but correctly keeps charging an edit for every
+/// candidate character that doesn't fit within budget — a banded update
+/// that stops widening once `i` runs past `|term|` would leave trailing
+/// cells frozen and accept arbitrarily long candidates that merely share a
+/// prefix with `term`.
+struct LevenshteinAutomaton<'a> {
+    term: &'a [char],
+    max_distance: usize,
+}
+
+impl<'a> LevenshteinAutomaton<'a> {
+    fn new(term: &'a [char], max_distance: usize) -> Self {
+        Self { term, max_distance }
+    }
+
+    fn matches(&self, candidate: &str) -> bool {
+        let n = self.term.len();
+        let mut row: Vec<usize> = (0..=n).collect();
+
+        for (i, c) in candidate.chars().enumerate() {
+            let mut next_row = vec![0; n + 1];
+            next_row[0] = i + 1;
+
+            for j in 0..n {
+                let cost = if self.term[j] == c { 0 } else { 1 };
+                let substitution = row[j] + cost;
+                let deletion = row[j + 1] + 1;
+                let insertion = next_row[j] + 1;
+                next_row[j + 1] = substitution.min(deletion).min(insertion);
+            }
+
+            if *next_row.iter().min().unwrap_or(&usize::MAX) > self.max_distance {
+                return false;
+            }
+
+            row = next_row;
+        }
+
+        row[n] <= self.max_distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_longer_candidate_sharing_only_a_prefix() {
+        let term: Vec<char> = "cat".chars().collect();
+        let automaton = LevenshteinAutomaton::new(&term, 0);
+
+        assert!(automaton.matches("cat"));
+        assert!(!automaton.matches("category"));
+        assert!(!automaton.matches("catastrophe"));
+    }
+
+    #[test]
+    fn accepts_within_budget_and_rejects_beyond_it() {
+        let term: Vec<char> = "kitten".chars().collect();
+        let automaton = LevenshteinAutomaton::new(&term, 2);
+
+        // "sitting" is 3 edits from "kitten" (k->s, e->i, +g), over budget.
+        assert!(!automaton.matches("sitting"));
+        // "sittin" is 2 edits from "kitten" (k->s, e->i), within budget.
+        assert!(automaton.matches("sittin"));
+    }
+}
+
+/// A single posting: which positions (word offsets) a term occurs at
+/// within a given chunk.
+pub(crate) type Postings = Vec<(usize, Vec<usize>)>;
+
+/// Inverted index over `ProcessedText` chunks, answering typo-tolerant
+/// queries via Levenshtein automata the way MeiliSearch-style engines do.
+pub struct SearchIndex {
+    /// term -> postings (chunk_id, positions within that chunk)
+    postings: HashMap<String, Postings>,
+    vocabulary: Vec<String>,
+    /// chunk_id -> chunk content, in ingestion order.
+    chunks: Vec<String>,
+    word_regex: Regex,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self {
+            postings: HashMap::new(),
+            vocabulary: Vec::new(),
+            chunks: Vec::new(),
+            word_regex: Regex::new(r"\b[a-zA-Z]+\b").unwrap(),
+        }
+    }
+
+    /// Index every chunk of every processed text.
+    pub fn ingest(&mut self, texts: &[ProcessedText]) {
+        for text in texts {
+            for chunk in &text.chunks {
+                self.add_chunk(chunk);
+            }
+        }
+    }
+
+    pub fn add_chunk(&mut self, content: &str) -> usize {
+        let chunk_id = self.chunks.len();
+        self.chunks.push(content.to_string());
+
+        for (position, raw) in self.word_regex.find_iter(content).enumerate() {
+            let word = raw.as_str().to_lowercase();
+
+            let postings = self.postings.entry(word.clone()).or_insert_with(Vec::new);
+            match postings.iter_mut().find(|(id, _)| *id == chunk_id) {
+                Some((_, positions)) => positions.push(position),
+                None => postings.push((chunk_id, vec![position])),
+            }
+
+            if let Err(idx) = self.vocabulary.binary_search(&word) {
+                self.vocabulary.insert(idx, word);
+            }
+        }
+
+        chunk_id
+    }
+
+    pub fn chunk(&self, chunk_id: usize) -> Option<&str> {
+        self.chunks.get(chunk_id).map(|s| s.as_str())
+    }
+
+    /// Whether `term` is present in the vocabulary exactly as given.
+    pub fn contains_term(&self, term: &str) -> bool {
+        self.vocabulary.binary_search(&term.to_string()).is_ok()
+    }
+
+    /// Total number of occurrences of `term` across all chunks.
+    pub fn term_frequency(&self, term: &str) -> usize {
+        self.postings
+            .get(term)
+            .map(|postings| postings.iter().map(|(_, positions)| positions.len()).sum())
+            .unwrap_or(0)
+    }
+
+    /// Candidate terms for a query word: vocabulary entries starting with
+    /// it when `prefix` is set, otherwise terms within its typo budget.
+    pub(crate) fn candidate_terms(&self, word: &str, prefix: bool) -> Vec<&str> {
+        if prefix {
+            self.vocabulary
+                .iter()
+                .filter(|t| t.starts_with(word))
+                .map(|s| s.as_str())
+                .collect()
+        } else {
+            self.fuzzy_terms(word, max_distance_for(word))
+        }
+    }
+
+    /// Raw postings for an exact term, if indexed.
+    pub(crate) fn postings(&self, term: &str) -> Option<&Postings> {
+        self.postings.get(term)
+    }
+
+    /// Find every indexed term within `max_distance` edits of `word`.
+    fn fuzzy_terms(&self, word: &str, max_distance: usize) -> Vec<&str> {
+        if max_distance == 0 {
+            return self
+                .vocabulary
+                .iter()
+                .filter(|t| t.as_str() == word)
+                .map(|s| s.as_str())
+                .collect();
+        }
+
+        let chars: Vec<char> = word.chars().collect();
+        let automaton = LevenshteinAutomaton::new(&chars, max_distance);
+
+        self.vocabulary
+            .iter()
+            .filter(|term| automaton.matches(term))
+            .map(|s| s.as_str())
+            .collect()
+    }
+
+    /// Parse a raw query string into an `Operation` tree: one `And` branch
+    /// per word, with stop words dropped and the last word treated as a
+    /// prefix match (so "comput" matches "computer"/"computation").
+    pub fn parse_query(&self, query: &str) -> Operation {
+        let words: Vec<String> = self
+            .word_regex
+            .find_iter(query)
+            .map(|m| m.as_str().to_lowercase())
+            .filter(|w| !STOP_WORDS.contains(&w.as_str()))
+            .collect();
+        let last_index = words.len().saturating_sub(1);
+
+        let leaves: Vec<Operation> = words
+            .into_iter()
+            .enumerate()
+            .map(|(i, term)| {
+                let prefix = i == last_index;
+                Operation::Query(Query {
+                    tolerant: !prefix,
+                    prefix,
+                    term,
+                })
+            })
+            .collect();
+
+        if leaves.len() == 1 {
+            leaves.into_iter().next().unwrap()
+        } else {
+            Operation::And(leaves)
+        }
+    }
+
+    /// Evaluate `query` end to end and return chunks ranked by number of
+    /// matched query words, then by summed term frequency.
+    pub fn search_query(&self, query: &str) -> Vec<(usize, f64)> {
+        self.evaluate(&self.parse_query(query))
+            .into_iter()
+            .map(|(chunk_id, _words_matched, freq)| (chunk_id, freq as f64))
+            .collect()
+    }
+
+    /// Tokenize `query` into lowercase words, dropping nothing — unlike
+    /// `parse_query`, stop words are kept, so callers that expand these
+    /// words through a `QueryEnhancer` filter stop words themselves first.
+    pub fn tokenize(&self, query: &str) -> Vec<String> {
+        self.word_regex
+            .find_iter(query)
+            .map(|m| m.as_str().to_lowercase())
+            .collect()
+    }
+
+    /// Evaluate an `Operation` tree, returning chunk ids with how many
+    /// distinct query words matched and the summed term frequency.
+    pub fn evaluate(&self, op: &Operation) -> Vec<(usize, usize, usize)> {
+        let matches = self.eval_op(op);
+
+        let mut scored: Vec<(usize, usize, usize)> = matches
+            .into_iter()
+            .map(|(chunk_id, (words_matched, freq))| (chunk_id, words_matched, freq))
+            .collect();
+
+        scored.sort_by(|a, b| a.1.cmp(&b.1).then(a.2.cmp(&b.2)).reverse());
+        scored
+    }
+
+    /// Recursively evaluate a query node, returning per-chunk (distinct
+    /// terms matched, summed term frequency).
+    fn eval_op(&self, op: &Operation) -> HashMap<usize, (usize, usize)> {
+        match op {
+            Operation::And(children) => {
+                let mut iter = children.iter().map(|c| self.eval_op(c));
+                let first = iter.next().unwrap_or_default();
+                iter.fold(first, |acc, next| {
+                    let mut merged = HashMap::new();
+                    for (chunk_id, (count, freq)) in acc {
+                        if let Some((other_count, other_freq)) = next.get(&chunk_id) {
+                            merged.insert(chunk_id, (count + other_count, freq + other_freq));
+                        }
+                    }
+                    merged
+                })
+            }
+            Operation::Or(children) => {
+                let mut merged: HashMap<usize, (usize, usize)> = HashMap::new();
+                for child in children {
+                    for (chunk_id, (count, freq)) in self.eval_op(child) {
+                        let entry = merged.entry(chunk_id).or_insert((0, 0));
+                        // Alternatives stand in for the same query word(s),
+                        // so a chunk matching several isn't counted twice.
+                        entry.0 = entry.0.max(count);
+                        entry.1 += freq;
+                    }
+                }
+                merged
+            }
+            Operation::Query(leaf) => self.eval_leaf(leaf),
+        }
+    }
+
+    /// Evaluate a single leaf: prefix leaves match any indexed term starting
+    /// with the leaf's text, tolerant leaves match via the Levenshtein
+    /// automaton. Returns chunk_id -> (1 matched word, summed frequency).
+    fn eval_leaf(&self, query: &Query) -> HashMap<usize, (usize, usize)> {
+        let terms: Vec<&str> = if query.prefix {
+            self.vocabulary
+                .iter()
+                .filter(|t| t.starts_with(&query.term))
+                .map(|s| s.as_str())
+                .collect()
+        } else if query.tolerant {
+            self.fuzzy_terms(&query.term, max_distance_for(&query.term))
+        } else {
+            self.vocabulary
+                .iter()
+                .filter(|t| t.as_str() == query.term)
+                .map(|s| s.as_str())
+                .collect()
+        };
+
+        let mut by_chunk: HashMap<usize, usize> = HashMap::new();
+        for term in terms {
+            if let Some(postings) = self.postings.get(term) {
+                for (chunk_id, positions) in postings {
+                    *by_chunk.entry(*chunk_id).or_insert(0) += positions.len();
+                }
+            }
+        }
+
+        by_chunk
+            .into_iter()
+            .map(|(chunk_id, freq)| (chunk_id, (1, freq)))
+            .collect()
+    }
+}
+
+impl Default for SearchIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,100 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Unicode-aware word segmenter shared by keyword extraction,
+/// summarization, and similarity scoring, so accented Latin (café),
+/// Cyrillic, Greek, and CJK text are tokenized instead of silently dropped
+/// by an ASCII-only regex.
+///
+/// Scripts that mark word boundaries with spaces are segmented with
+/// `unicode-segmentation`'s UAX #29 word breaker. Scripts that don't
+/// (Han, Hiragana, Katakana, Hangul) are detected per codepoint and
+/// tokenized as character bigrams instead, since there's no boundary
+/// information to split on otherwise.
+pub struct Tokenizer;
+
+impl Tokenizer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Split `text` into lowercase tokens: Unicode words for space-delimited
+    /// scripts, character bigrams (or single characters, for isolated runs)
+    /// for CJK scripts.
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut run = String::new();
+        let mut run_is_cjk = false;
+
+        for c in text.chars() {
+            let is_cjk = is_cjk_codepoint(c);
+            if !run.is_empty() && is_cjk != run_is_cjk {
+                Self::tokenize_run(&run, run_is_cjk, &mut tokens);
+                run.clear();
+            }
+            run_is_cjk = is_cjk;
+            run.push(c);
+        }
+        Self::tokenize_run(&run, run_is_cjk, &mut tokens);
+
+        tokens
+    }
+
+    fn tokenize_run(run: &str, is_cjk: bool, tokens: &mut Vec<String>) {
+        if run.is_empty() {
+            return;
+        }
+        if is_cjk {
+            tokens.extend(Self::cjk_bigrams(run));
+        } else {
+            tokens.extend(run.unicode_words().map(|w| w.to_lowercase()));
+        }
+    }
+
+    fn cjk_bigrams(run: &str) -> Vec<String> {
+        let chars: Vec<char> = run.chars().collect();
+        if chars.len() <= 1 {
+            return chars.iter().map(|c| c.to_string()).collect();
+        }
+        chars.windows(2).map(|pair| pair.iter().collect()).collect()
+    }
+
+    /// Whether `token` is substantial enough to count as a keyword
+    /// candidate. CJK bigrams/characters carry meaning at any length, so
+    /// only non-CJK tokens are held to the "longer than two characters"
+    /// bar English/Latin-script stop-word filtering relies on.
+    pub fn is_meaningful(&self, token: &str) -> bool {
+        match token.chars().next() {
+            Some(c) if is_cjk_codepoint(c) => true,
+            _ => token.chars().count() > 2,
+        }
+    }
+
+    /// Script-aware stop-word check: CJK tokens never match a Latin
+    /// stop-word list, so only non-CJK tokens are looked up at all.
+    pub fn is_stop_word(&self, token: &str, stop_words: &[String]) -> bool {
+        match token.chars().next() {
+            Some(c) if is_cjk_codepoint(c) => false,
+            _ => stop_words.iter().any(|stop_word| stop_word == token),
+        }
+    }
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether `c` belongs to a script that's conventionally written without
+/// spaces between words (Han, Hiragana, Katakana, Hangul).
+fn is_cjk_codepoint(c: char) -> bool {
+    matches!(c as u32,
+        0x4E00..=0x9FFF   // CJK Unified Ideographs
+        | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+        | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+        | 0x3040..=0x309F // Hiragana
+        | 0x30A0..=0x30FF // Katakana
+        | 0xAC00..=0xD7A3 // Hangul Syllables
+        | 0x1100..=0x11FF // Hangul Jamo
+    )
+}
@@ -0,0 +1,359 @@
+use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
+use reqwest::{Client, StatusCode};
+use reqwest_cookie_store::{CookieStore, CookieStoreMutex};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrapedData {
+    pub url: String,
+    pub title: String,
+    pub content: String,
+    pub metadata: HashMap<String, String>,
+}
+
+/// Persists a cookie jar to a JSON file on disk, so an authenticated
+/// session survives across `scrape_url` calls and process restarts.
+pub struct CookieStorage {
+    path: PathBuf,
+    store: Arc<CookieStoreMutex>,
+}
+
+impl CookieStorage {
+    /// Load cookies from `path` if it already exists, otherwise start with
+    /// an empty jar that will be written there on the next `save`.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let store = if path.exists() {
+            let file = BufReader::new(File::open(&path)?);
+            CookieStore::load_json(file).map_err(|e| anyhow!("failed to load cookie jar: {}", e))?
+        } else {
+            CookieStore::default()
+        };
+
+        Ok(Self {
+            path,
+            store: Arc::new(CookieStoreMutex::new(store)),
+        })
+    }
+
+    /// Persist the current cookie jar to disk.
+    pub fn save(&self) -> Result<()> {
+        let store = self
+            .store
+            .lock()
+            .map_err(|_| anyhow!("cookie jar lock poisoned"))?;
+        let mut file = File::create(&self.path)?;
+        store
+            .save_json(&mut file)
+            .map_err(|e| anyhow!("failed to save cookie jar: {}", e))?;
+        Ok(())
+    }
+
+    fn provider(&self) -> Arc<CookieStoreMutex> {
+        self.store.clone()
+    }
+}
+
+/// Retry policy for transient failures (429 / 5xx) hit mid-batch: retried
+/// with exponential backoff rather than failing the whole batch.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// A 429 or 5xx response, distinguished from other HTTP errors so retry
+/// logic knows which failures are worth backing off and trying again.
+#[derive(Debug)]
+struct TransientHttpError(StatusCode);
+
+impl fmt::Display for TransientHttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transient HTTP error: {}", self.0)
+    }
+}
+
+impl std::error::Error for TransientHttpError {}
+
+pub struct WebScraper {
+    client: Client,
+    rate_limit_delay: Duration,
+    cookies: Option<CookieStorage>,
+    retry: RetryPolicy,
+}
+
+impl WebScraper {
+    pub fn new() -> Self {
+        let client = Self::build_client(None);
+
+        Self {
+            client,
+            rate_limit_delay: Duration::from_millis(100), // 10 requests per second
+            cookies: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Build a scraper whose cookies persist to `cookie_jar_path` via the
+    /// client's cookie store, so logged-in sessions survive across
+    /// `scrape_url` calls and process restarts.
+    pub fn with_cookie_storage(cookie_jar_path: impl Into<PathBuf>) -> Result<Self> {
+        let cookies = CookieStorage::load(cookie_jar_path)?;
+        let client = Self::build_client(Some(cookies.provider()));
+
+        Ok(Self {
+            client,
+            rate_limit_delay: Duration::from_millis(100),
+            cookies: Some(cookies),
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Override the default retry policy for transient (429/5xx) failures.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn build_client(cookie_provider: Option<Arc<CookieStoreMutex>>) -> Client {
+        let mut builder = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("Mozilla/5.0 (compatible; NocturnalArchive/1.0; +https://nocturnalarchive.com/bot)");
+
+        if let Some(provider) = cookie_provider {
+            builder = builder.cookie_provider(provider);
+        }
+
+        builder.build().unwrap_or_else(|_| Client::new())
+    }
+
+    /// GET `login_url`, extract a CSRF/hidden token via `csrf_selector`
+    /// (when the login form needs one), POST `form_fields` plus that token
+    /// under `csrf_field_name`, and verify success by checking for a
+    /// redirect away from `login_url` (the client follows redirects by
+    /// default, so `response.url()` reflects the final location) or for
+    /// `success_selector` matching the resulting page. On success the
+    /// cookie jar, if any, is persisted immediately.
+    pub async fn login(
+        &self,
+        login_url: &str,
+        form_fields: &HashMap<String, String>,
+        csrf_selector: Option<(&Selector, &str)>,
+        success_selector: Option<&Selector>,
+    ) -> Result<()> {
+        let login_page = self.client.get(login_url).send().await?;
+        let login_html = login_page.text().await?;
+        let document = Html::parse_document(&login_html);
+
+        let mut form = form_fields.clone();
+        if let Some((selector, csrf_field_name)) = csrf_selector {
+            let token = document
+                .select(selector)
+                .next()
+                .and_then(|el| el.value().attr("value"))
+                .ok_or_else(|| anyhow!("CSRF token not found via the given selector"))?;
+            form.insert(csrf_field_name.to_string(), token.to_string());
+        }
+
+        let response = self.client.post(login_url).form(&form).send().await?;
+        let redirected = response.url().as_str() != login_url;
+        let body = response.text().await?;
+
+        let matched_success_selector = success_selector
+            .map(|selector| Html::parse_document(&body).select(selector).next().is_some())
+            .unwrap_or(false);
+
+        if !redirected && !matched_success_selector {
+            return Err(anyhow!(
+                "login to {} did not redirect and did not match the success selector",
+                login_url
+            ));
+        }
+
+        if let Some(cookies) = &self.cookies {
+            cookies.save()?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn scrape_url(&self, url: &str) -> Result<ScrapedData> {
+        // Rate limiting
+        sleep(self.rate_limit_delay).await;
+        self.scrape_with_retries(url).await
+    }
+
+    async fn scrape_with_retries(&self, url: &str) -> Result<ScrapedData> {
+        let mut attempt = 0;
+        loop {
+            match self.scrape_url_once(url).await {
+                Ok(data) => return Ok(data),
+                Err(err)
+                    if attempt < self.retry.max_retries && err.is::<TransientHttpError>() =>
+                {
+                    let delay = self.retry.base_delay * 2u32.pow(attempt);
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    async fn scrape_url_once(&self, url: &str) -> Result<ScrapedData> {
+        let response = self.client.get(url).send().await?;
+        let status = response.status();
+
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            return Err(TransientHttpError(status).into());
+        }
+        if !status.is_success() {
+            return Err(anyhow!("HTTP error: {}", status));
+        }
+
+        let html = response.text().await?;
+        let document = Html::parse_document(&html);
+
+        // Extract title
+        let title_selector = Selector::parse("title").unwrap();
+        let title = document
+            .select(&title_selector)
+            .next()
+            .map(|el| el.inner_html())
+            .unwrap_or_else(|| "No title".to_string());
+
+        // Extract main content (prioritize article, main, or body)
+        let content_selectors = [
+            "article",
+            "main",
+            "[role='main']",
+            ".content",
+            ".main-content",
+            "body"
+        ];
+
+        let mut content = String::new();
+        for selector_str in &content_selectors {
+            if let Ok(selector) = Selector::parse(selector_str) {
+                if let Some(element) = document.select(&selector).next() {
+                    content = self.clean_html_content(&element.inner_html());
+                    if !content.is_empty() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Extract metadata
+        let mut metadata = HashMap::new();
+
+        // Meta tags
+        let meta_selector = Selector::parse("meta").unwrap();
+        for meta in document.select(&meta_selector) {
+            if let (Some(name), Some(content)) = (
+                meta.value().attr("name").or(meta.value().attr("property")),
+                meta.value().attr("content")
+            ) {
+                metadata.insert(name.to_string(), content.to_string());
+            }
+        }
+
+        // Open Graph tags
+        let og_selector = Selector::parse("[property^='og:']").unwrap();
+        for og in document.select(&og_selector) {
+            if let (Some(property), Some(content)) = (
+                og.value().attr("property"),
+                og.value().attr("content")
+            ) {
+                metadata.insert(property.to_string(), content.to_string());
+            }
+        }
+
+        Ok(ScrapedData {
+            url: url.to_string(),
+            title,
+            content,
+            metadata,
+        })
+    }
+
+    fn clean_html_content(&self, html: &str) -> String {
+        // Remove script and style tags
+        let re_script = regex::Regex::new(r"<script[^>]*>.*?</script>").unwrap();
+        let re_style = regex::Regex::new(r"<style[^>]*>.*?</style>").unwrap();
+        let re_nav = regex::Regex::new(r"<nav[^>]*>.*?</nav>").unwrap();
+        let re_header = regex::Regex::new(r"<header[^>]*>.*?</header>").unwrap();
+        let re_footer = regex::Regex::new(r"<footer[^>]*>.*?</footer>").unwrap();
+        let re_ads = regex::Regex::new(r"<div[^>]*class[^>]*ad[^>]*>.*?</div>").unwrap();
+
+        let mut cleaned = html.to_string();
+        cleaned = re_script.replace_all(&cleaned, "").to_string();
+        cleaned = re_style.replace_all(&cleaned, "").to_string();
+        cleaned = re_nav.replace_all(&cleaned, "").to_string();
+        cleaned = re_header.replace_all(&cleaned, "").to_string();
+        cleaned = re_footer.replace_all(&cleaned, "").to_string();
+        cleaned = re_ads.replace_all(&cleaned, "").to_string();
+
+        // Convert HTML to text
+        html2text::from_read(cleaned.as_bytes(), 80)
+    }
+
+    pub async fn scrape_urls_batch(&self, urls: &[String]) -> Result<Vec<ScrapedData>> {
+        // Bound concurrency so we don't overwhelm servers, rather than
+        // joining fixed-size chunks (which stalls the whole batch on its
+        // slowest member).
+        let batch_size = 5;
+        let results = stream::iter(urls.iter().cloned())
+            .map(|url| {
+                let scraper = self.clone();
+                async move { scraper.scrape_url(&url).await }
+            })
+            .buffer_unordered(batch_size)
+            .filter_map(|result| async move {
+                match result {
+                    Ok(data) => Some(data),
+                    Err(e) => {
+                        eprintln!("Error scraping URL: {}", e);
+                        None
+                    }
+                }
+            })
+            .collect()
+            .await;
+
+        Ok(results)
+    }
+}
+
+impl Clone for WebScraper {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            rate_limit_delay: self.rate_limit_delay,
+            cookies: self.cookies.as_ref().map(|c| CookieStorage {
+                path: c.path.clone(),
+                store: c.store.clone(),
+            }),
+            retry: self.retry,
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Pure recursive-split + overlap chunking algorithm, shared verbatim by
+//! `core::chunker::Chunker` and `rust_performance`'s `Chunker` (which
+//! includes this file directly via `#[path]`, since the two crates don't
+//! share a dependency graph) — a fix to the splitting logic only has to be
+//! made once instead of twice. Operates purely on byte-offset spans over
+//! `&str` plus a `measure` callback, so each crate can wrap it with
+//! whatever size unit and output type (`TextChunk` vs. plain `String`) it
+//! needs.
+
+/// Split `text` into spans under `target_size` (as reported by `measure`),
+/// then greedily merge them back into overlapping chunks of roughly that
+/// size.
+pub(crate) fn compute_spans(
+    text: &str,
+    target_size: usize,
+    overlap: usize,
+    measure: &dyn Fn(&str) -> usize,
+) -> Vec<(usize, usize)> {
+    let atoms = split_recursive(text, 0, 0, target_size, measure);
+    merge_with_overlap(text, atoms, target_size, overlap, measure)
+}
+
+/// Split `text` (which starts at `base_offset` in the original document) on
+/// the separator for the current priority `level`, recursing into any piece
+/// that's still too large. Falls through paragraph -> line -> sentence ->
+/// whitespace -> hard split.
+fn split_recursive(
+    text: &str,
+    base_offset: usize,
+    level: usize,
+    target_size: usize,
+    measure: &dyn Fn(&str) -> usize,
+) -> Vec<(usize, usize)> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    if measure(text) <= target_size {
+        return vec![(base_offset, base_offset + text.len())];
+    }
+
+    let parts: Vec<&str> = match level {
+        0 => text.split_inclusive("\n\n").collect(),
+        1 => text.split_inclusive('\n').collect(),
+        2 => text.split_inclusive(['.', '!', '?'].as_ref()).collect(),
+        3 => text.split_inclusive(' ').collect(),
+        _ => return hard_split(text, base_offset, target_size, measure),
+    };
+
+    if parts.len() <= 1 {
+        return split_recursive(text, base_offset, level + 1, target_size, measure);
+    }
+
+    let mut spans = Vec::new();
+    let mut offset = base_offset;
+    for part in parts {
+        spans.extend(split_recursive(part, offset, level + 1, target_size, measure));
+        offset += part.len();
+    }
+    spans
+}
+
+/// Last resort when a single "word" exceeds the target size on its own:
+/// cut at the target size (in whatever unit `measure` reports, characters or
+/// an estimated token count) rather than leaving an oversized chunk.
+fn hard_split(
+    text: &str,
+    base_offset: usize,
+    target_size: usize,
+    measure: &dyn Fn(&str) -> usize,
+) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+
+    for (byte_idx, ch) in text.char_indices() {
+        let end = byte_idx + ch.len_utf8();
+        if measure(&text[start..end]) > target_size {
+            spans.push((base_offset + start, base_offset + byte_idx));
+            start = byte_idx;
+        }
+    }
+    if start < text.len() {
+        spans.push((base_offset + start, base_offset + text.len()));
+    }
+    spans
+}
+
+/// Greedily pack atoms into chunks under the target size, carrying
+/// `overlap` characters from the tail of one chunk into the next.
+fn merge_with_overlap(
+    text: &str,
+    atoms: Vec<(usize, usize)>,
+    target_size: usize,
+    overlap: usize,
+    measure: &dyn Fn(&str) -> usize,
+) -> Vec<(usize, usize)> {
+    let mut chunks = Vec::new();
+    let mut current_start: Option<usize> = None;
+    let mut current_end = 0;
+
+    for (start, end) in atoms {
+        let chunk_start = *current_start.get_or_insert(start);
+
+        if current_end > chunk_start && measure(&text[chunk_start..end]) > target_size {
+            chunks.push((chunk_start, current_end));
+            // Shrink the carried overlap so it plus this atom still fits
+            // under the target size.
+            let budget = overlap.min(target_size.saturating_sub(measure(&text[start..end])));
+            current_start = Some(overlap_start(text, current_end, budget, measure));
+        }
+
+        current_end = end;
+    }
+
+    if let Some(start) = current_start {
+        if current_end > start {
+            chunks.push((start, current_end));
+        }
+    }
+
+    chunks
+}
+
+/// Walk backward from `end` one character at a time, via `measure`, until
+/// the trailing span would cost more than `overlap` in whatever unit the
+/// caller's `measure` reports (characters or an estimated token count) —
+/// so `overlap` is always interpreted in the same unit as `target_size`,
+/// rather than always as a raw character count.
+fn overlap_start(text: &str, end: usize, overlap: usize, measure: &dyn Fn(&str) -> usize) -> usize {
+    if overlap == 0 {
+        return end;
+    }
+    let mut start = end;
+    while let Some((prev_idx, _)) = text[..start].char_indices().next_back() {
+        if measure(&text[prev_idx..end]) > overlap {
+            break;
+        }
+        start = prev_idx;
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> usize {
+        s.chars().count()
+    }
+
+    /// Same token-estimate formula as `core::chunker::Chunker::measure`.
+    fn tokens(s: &str) -> usize {
+        (s.chars().count() + 3) / 4
+    }
+
+    #[test]
+    fn split_recursive_keeps_pieces_under_target_by_characters() {
+        let text = "one two three.\nfour five six.\nseven eight nine.";
+        let spans = split_recursive(text, 0, 0, 15, &chars);
+
+        for (start, end) in &spans {
+            assert!(chars(&text[*start..*end]) <= 15 || !text[*start..*end].contains(' '));
+        }
+        let rebuilt: String = spans.iter().map(|(s, e)| &text[*s..*e]).collect();
+        assert_eq!(rebuilt, text);
+    }
+
+    #[test]
+    fn split_recursive_keeps_pieces_under_target_by_tokens() {
+        let text = "one two three.\nfour five six.\nseven eight nine.";
+        let target = 8; // ~32 characters
+        let spans = split_recursive(text, 0, 0, target, &tokens);
+
+        for (start, end) in &spans {
+            let piece = &text[*start..*end];
+            assert!(tokens(piece) <= target || !piece.contains(' '));
+        }
+    }
+
+    #[test]
+    fn hard_split_pins_target_size_in_characters() {
+        // A single "word" with no separators at all, longer than the target.
+        let word = "a".repeat(10);
+        let spans = hard_split(&word, 0, 3, &chars);
+
+        for (start, end) in &spans {
+            assert!(chars(&word[*start..*end]) <= 3);
+        }
+        let rebuilt: String = spans.iter().map(|(s, e)| &word[*s..*e]).collect();
+        assert_eq!(rebuilt, word);
+    }
+
+    #[test]
+    fn hard_split_pins_target_size_in_tokens() {
+        // With SizeUnit::Tokens, target_size is ~1/4 of the equivalent
+        // character count — hard_split must consult `measure` rather than
+        // counting raw chars, or it cuts ~4x smaller than requested.
+        let word = "a".repeat(40);
+        let target = 10; // ~40 characters
+        let spans = hard_split(&word, 0, target, &tokens);
+
+        assert_eq!(spans.len(), 1, "40 chars is exactly 10 tokens, shouldn't split");
+        for (start, end) in &spans {
+            assert!(tokens(&word[*start..*end]) <= target);
+        }
+    }
+
+    #[test]
+    fn overlap_start_stays_on_char_boundaries_for_multibyte_text() {
+        // CJK text: each character is several UTF-8 bytes, with no ASCII
+        // whitespace for char_indices() to align against.
+        let text = "一二三四五六七八九十";
+        let end = text.len();
+
+        let start = overlap_start(text, end, 3, &chars);
+        // Must land on a char boundary (would panic on slicing otherwise)
+        // and carry at most the requested overlap.
+        assert!(text.is_char_boundary(start));
+        assert!(chars(&text[start..end]) <= 3);
+        assert!(start < end);
+    }
+
+    #[test]
+    fn overlap_start_respects_token_unit() {
+        let text = "abcdefghijklmnop";
+        let end = text.len();
+
+        // 2 tokens ~= 8 characters.
+        let start = overlap_start(text, end, 2, &tokens);
+        assert!(tokens(&text[start..end]) <= 2);
+    }
+
+    #[test]
+    fn merge_with_overlap_carries_requested_overlap_between_chunks() {
+        let text = "aaaa bbbb cccc dddd";
+        let atoms = split_recursive(text, 0, 0, 9, &chars);
+        let chunks = merge_with_overlap(text, atoms, 9, 4, &chars);
+
+        assert!(chunks.len() >= 2);
+        for (start, end) in &chunks {
+            assert!(chars(&text[*start..*end]) <= 9);
+        }
+        for pair in chunks.windows(2) {
+            let (prev_end, next_start) = (pair[0].1, pair[1].0);
+            assert!(next_start < prev_end);
+            assert!(chars(&text[next_start..prev_end]) <= 4);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::chunk_spans::compute_spans;
+use crate::TextChunk;
+
+/// Unit the target chunk size is measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnit {
+    Characters,
+    /// Token count is estimated as roughly one token per four characters.
+    Tokens,
+}
+
+/// Recursive-split chunker: breaks text on the highest-priority separator
+/// that still keeps pieces under the target size (paragraph breaks, then
+/// lines, then sentence boundaries, then whitespace), cutting inside a word
+/// only when a single word exceeds the target size, and tracks each chunk's
+/// source offset range. The overlap carried between chunks is measured in
+/// the size unit, so it can begin part-way through a word. The splitting
+/// algorithm itself lives in `chunk_spans`, shared with
+/// `rust_performance`'s `Chunker`.
+pub struct Chunker {
+    target_size: usize,
+    overlap: usize,
+    unit: SizeUnit,
+}
+
+impl Chunker {
+    pub fn new(target_size: usize, overlap: usize, unit: SizeUnit) -> Self {
+        Self {
+            target_size: target_size.max(1),
+            overlap,
+            unit,
+        }
+    }
+
+    pub fn chunk(&self, text: &str) -> Vec<TextChunk> {
+        let measure = |s: &str| self.measure(s);
+        compute_spans(text, self.target_size, self.overlap, &measure)
+            .into_iter()
+            .enumerate()
+            .map(|(index, (start, end))| self.make_chunk(text, start, end, index))
+            .collect()
+    }
+
+    fn measure(&self, text: &str) -> usize {
+        match self.unit {
+            SizeUnit::Characters => text.chars().count(),
+            SizeUnit::Tokens => (text.chars().count() + 3) / 4,
+        }
+    }
+
+    fn make_chunk(&self, text: &str, start: usize, end: usize, index: usize) -> TextChunk {
+        TextChunk {
+            content: text[start..end].trim().to_string(),
+            index,
+            embedding: None,
+            start,
+            end,
+        }
+    }
+}
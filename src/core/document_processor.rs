@@ -1,20 +1,26 @@
+use crate::chunker::{Chunker, SizeUnit};
+use crate::trends::TrendTracker;
 use crate::{Document, ProcessedDocument, Result, TextChunk};
 use pdf_extract;
+use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
 use std::sync::Arc;
 use tokio;
 use tracing::{info, error, instrument};
 
 pub struct DocumentProcessor {
-    redis_client: Arc<redis::Client>,
+    redis: Arc<ConnectionManager>,
+    trends: TrendTracker,
 }
 
 impl DocumentProcessor {
-    pub fn new(redis_url: &str) -> Result<Self> {
+    pub async fn new(redis_url: &str) -> Result<Self> {
         info!("Initializing DocumentProcessor with Redis URL: {}", redis_url);
         let client = redis::Client::open(redis_url)?;
+        let redis = Arc::new(ConnectionManager::new(client).await?);
         Ok(Self {
-            redis_client: Arc::new(client),
+            trends: TrendTracker::new(redis.clone()),
+            redis,
         })
     }
 
@@ -46,6 +52,13 @@ impl DocumentProcessor {
             metadata: document.metadata,
         };
 
+        info!("Recording terms for trending");
+        let terms: Vec<String> = crate::search::tokenize(&processed.text_content)
+            .into_iter()
+            .filter(|term| !crate::search::is_stop_word(term))
+            .collect();
+        self.trends.record_terms(&processed.doc_id, &terms).await?;
+
         info!("Queueing document for LLM processing");
         self.queue_for_processing(&processed).await?;
         info!("Document successfully queued");
@@ -70,25 +83,19 @@ impl DocumentProcessor {
 
     fn create_chunks(&self, text: &str) -> Vec<TextChunk> {
         info!("Splitting text into chunks");
-        let chunks: Vec<TextChunk> = text.split('\n')
-            .enumerate()
-            .map(|(i, content)| TextChunk {
-                content: content.to_string(),
-                index: i,
-            })
-            .collect();
+        let chunker = Chunker::new(1000, 200, SizeUnit::Characters);
+        let chunks = chunker.chunk(text);
         info!("Created {} text chunks", chunks.len());
         chunks
     }
 
     #[instrument(skip(self, doc))]
     async fn queue_for_processing(&self, doc: &ProcessedDocument) -> Result<()> {
-        info!("Connecting to Redis");
-        let mut conn = self.redis_client.get_async_connection().await?;
-        
+        let mut conn = (*self.redis).clone();
+
         info!("Serializing document for queue");
         let serialized = serde_json::to_string(&doc)?;
-        
+
         info!("Adding document to processing queue");
         conn.lpush("processing_queue", serialized).await?;
         info!("Document successfully queued");
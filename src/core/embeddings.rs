@@ -0,0 +1,157 @@
+use crate::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use std::time::Duration;
+
+/// Produces dense embedding vectors for text, so chunks can be ranked by
+/// semantic similarity in addition to the lexical `SearchIndex`.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Deterministic, dependency-free embedder using the hashing trick: each
+/// word is hashed into a fixed-size vector slot with a sign derived from
+/// the hash, giving a stable local stand-in for a real ONNX encoder.
+pub struct LocalEmbedder {
+    dimensions: usize,
+}
+
+impl LocalEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+
+    fn hash_embed(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0f32; self.dimensions];
+
+        for word in crate::search::tokenize(text) {
+            let hash = fnv1a(&word);
+            let idx = (hash as usize) % self.dimensions;
+            let sign = if hash & 1 == 0 { 1.0 } else { -1.0 };
+            vector[idx] += sign;
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+}
+
+fn fnv1a(s: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in s.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[async_trait]
+impl Embedder for LocalEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|t| self.hash_embed(t)).collect())
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct EmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Calls out to a configurable embedding endpoint, batched, with the same
+/// retry/backoff shape the scraper uses for transient failures.
+pub struct HttpEmbedder {
+    client: Client,
+    endpoint: String,
+    batch_size: usize,
+    max_retries: u32,
+}
+
+impl HttpEmbedder {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            endpoint: endpoint.into(),
+            batch_size: 32,
+            max_retries: 3,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    async fn embed_batch(&self, batch: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut attempt = 0;
+
+        loop {
+            let request = self
+                .client
+                .post(&self.endpoint)
+                .json(&serde_json::json!({ "input": batch }))
+                .send()
+                .await;
+
+            match request {
+                Ok(response) if response.status().is_success() => {
+                    let parsed: EmbeddingResponse = response.json().await?;
+                    return Ok(parsed.embeddings);
+                }
+                Ok(response)
+                    if attempt < self.max_retries
+                        && (response.status().is_server_error()
+                            || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS) =>
+                {
+                    attempt += 1;
+                    tokio::time::sleep(Duration::from_millis(200 * 2u64.pow(attempt))).await;
+                }
+                Ok(response) => {
+                    return Err(format!("embedding endpoint returned {}", response.status()).into())
+                }
+                Err(_) if attempt < self.max_retries => {
+                    attempt += 1;
+                    tokio::time::sleep(Duration::from_millis(200 * 2u64.pow(attempt))).await;
+                }
+                Err(e) => return Err(Box::new(e)),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for HttpEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut results = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.batch_size) {
+            let embeddings = self.embed_batch(chunk).await?;
+            results.extend(embeddings);
+        }
+        Ok(results)
+    }
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+}
+
+/// Cosine similarity between two equal-length embedding vectors.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
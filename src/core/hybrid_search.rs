@@ -0,0 +1,94 @@
+use crate::embeddings::{cosine_similarity, Embedder};
+use crate::search::{SearchHit, SearchIndex};
+use crate::{ProcessedDocument, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Combines the lexical `SearchIndex` with per-chunk embedding vectors,
+/// fusing keyword and semantic retrieval into a single ranked result list.
+pub struct HybridSearch {
+    lexical: SearchIndex,
+    embedder: Arc<dyn Embedder>,
+    vectors: HashMap<(String, usize), Vec<f32>>,
+}
+
+impl HybridSearch {
+    pub fn new(embedder: Arc<dyn Embedder>) -> Self {
+        Self {
+            lexical: SearchIndex::new(),
+            embedder,
+            vectors: HashMap::new(),
+        }
+    }
+
+    /// Index a document both lexically and by embedding each of its chunks.
+    pub async fn add_document(&mut self, doc: &ProcessedDocument) -> Result<()> {
+        self.lexical.add_document(doc);
+
+        let texts: Vec<String> = doc.chunks.iter().map(|c| c.content.clone()).collect();
+        let embeddings = self.embedder.embed(&texts).await?;
+
+        for (chunk, embedding) in doc.chunks.iter().zip(embeddings) {
+            self.vectors
+                .insert((doc.doc_id.clone(), chunk.index), embedding);
+        }
+
+        Ok(())
+    }
+
+    /// Run lexical and semantic retrieval and fuse the rankings via a
+    /// normalized-score weighted sum. `semantic_ratio` of 0.0 is pure
+    /// keyword search, 1.0 is pure vector search.
+    pub async fn search(
+        &self,
+        query: &str,
+        semantic_ratio: f32,
+        limit: usize,
+    ) -> Result<Vec<(String, usize, f32)>> {
+        let semantic_ratio = semantic_ratio.clamp(0.0, 1.0);
+
+        let lexical_hits = self.lexical.search(query);
+        let lexical_scored = Self::normalize_lexical(&lexical_hits);
+
+        let query_embedding = self.embedder.embed(&[query.to_string()]).await?.remove(0);
+        let semantic_scored: Vec<((String, usize), f32)> = self
+            .vectors
+            .iter()
+            .map(|((doc_id, idx), vector)| {
+                ((doc_id.clone(), *idx), cosine_similarity(&query_embedding, vector))
+            })
+            .collect();
+
+        let mut fused: HashMap<(String, usize), f32> = HashMap::new();
+        for (key, score) in lexical_scored {
+            *fused.entry(key).or_insert(0.0) += (1.0 - semantic_ratio) * score;
+        }
+        for (key, score) in semantic_scored {
+            *fused.entry(key).or_insert(0.0) += semantic_ratio * score;
+        }
+
+        let mut ranked: Vec<(String, usize, f32)> = fused
+            .into_iter()
+            .map(|((doc_id, idx), score)| (doc_id, idx, score))
+            .collect();
+        ranked.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.truncate(limit);
+
+        Ok(ranked)
+    }
+
+    /// Expand each doc-level `SearchHit` into per-chunk scores normalized to
+    /// [0, 1] so they're comparable with cosine similarity.
+    fn normalize_lexical(hits: &[SearchHit]) -> Vec<((String, usize), f32)> {
+        let max_terms = hits.iter().map(|h| h.matched_terms).max().unwrap_or(1).max(1) as f32;
+
+        hits.iter()
+            .flat_map(|hit| {
+                let score = hit.matched_terms as f32 / max_terms;
+                hit.chunk_indices
+                    .iter()
+                    .map(move |&idx| ((hit.doc_id.clone(), idx), score))
+            })
+            .collect()
+    }
+}
@@ -1,6 +1,31 @@
 use serde::{Deserialize, Serialize};
 pub use std::error::Error;
 
+mod chunk_spans;
+pub mod chunker;
+pub mod document_processor;
+pub mod embeddings;
+pub mod error;
+pub mod hybrid_search;
+pub mod metrics;
+pub mod queue_handler;
+pub mod research_manager;
+pub mod search;
+pub mod trends;
+pub mod worker;
+
+pub use chunker::{Chunker, SizeUnit};
+pub use document_processor::DocumentProcessor;
+pub use embeddings::{Embedder, HttpEmbedder, LocalEmbedder};
+pub use error::ProcessingError;
+pub use hybrid_search::HybridSearch;
+pub use self::metrics::{install as install_metrics, MetricsHandle};
+pub use queue_handler::{QueueHandler, QueueItem, RetryQueueWorker};
+pub use research_manager::{ResearchManager, ResearchQueueWorker};
+pub use search::{Operation, QueryKind, SearchHit, SearchIndex};
+pub use trends::TrendTracker;
+pub use worker::{ShutdownToken, Worker, WorkerConfig, WorkerManager, WorkerState};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Document {
     pub id: String,
@@ -28,6 +53,15 @@ pub struct ProcessedDocument {
 pub struct TextChunk {
     pub content: String,
     pub index: usize,
+    /// Dense embedding for this chunk, when `HybridSearch` has indexed it.
+    #[serde(default)]
+    pub embedding: Option<Vec<f32>>,
+    /// Byte offset range `[start, end)` of this chunk within the source
+    /// document's text, so search hits can be mapped back to it.
+    #[serde(default)]
+    pub start: usize,
+    #[serde(default)]
+    pub end: usize,
 }
 
 pub type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
\ No newline at end of file
@@ -0,0 +1,27 @@
+use crate::error::{ProcessingError, Result};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+/// Thin wrapper around the installed Prometheus recorder, so callers can
+/// render the text exposition format for a scrape endpoint without
+/// depending on `metrics_exporter_prometheus` directly.
+#[derive(Clone)]
+pub struct MetricsHandle(PrometheusHandle);
+
+impl MetricsHandle {
+    /// Render every counter/gauge/histogram recorded so far in the
+    /// Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        self.0.render()
+    }
+}
+
+/// Install the process-wide metrics recorder. Call this once, early in the
+/// process's life — before any `counter!`/`gauge!`/`histogram!` call
+/// elsewhere in the crate, since those record against whatever recorder is
+/// globally installed at the time they run.
+pub fn install() -> Result<MetricsHandle> {
+    let handle = PrometheusBuilder::new()
+        .install_recorder()
+        .map_err(|e| ProcessingError::System(format!("failed to install metrics recorder: {}", e)))?;
+    Ok(MetricsHandle(handle))
+}
@@ -1,7 +1,12 @@
 use crate::error::{ProcessingError, Result};
+use crate::worker::{Worker, WorkerState};
+use async_trait::async_trait;
+use metrics::{gauge, histogram};
+use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 use tracing::{info, error, instrument};
 
@@ -13,45 +18,122 @@ pub struct QueueItem {
     pub payload: serde_json::Value,
 }
 
+/// Which Redis structure backs the main queue. `List` is the original
+/// FIFO-only `LPUSH`/`RPOP` behavior; `Priority` honors `QueueItem.priority`
+/// via a sorted set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueMode {
+    List,
+    Priority,
+}
+
+impl Default for QueueMode {
+    fn default() -> Self {
+        QueueMode::List
+    }
+}
+
+/// Set of worker names that have ever dequeued reliably, so `reap_stale`
+/// knows which per-worker processing lists to scan.
+const PROCESSING_WORKERS_SET: &str = "processing_workers";
+
+const DEAD_LETTER_QUEUE: &str = "dead_letter_queue";
+
+/// Backs `dequeue_reliable`: `LMOVE`s an item onto the worker's processing
+/// list, then `SADD`s the worker into `PROCESSING_WORKERS_SET` and `SET`s
+/// its visibility-timeout timestamp, all as one atomic `EVAL` so `reap_stale`
+/// never observes the item without its timestamp. KEYS: main queue,
+/// processing list, `PROCESSING_WORKERS_SET`. ARGV: worker name, now (unix
+/// seconds). The timestamp key is built to match `Self::timestamp_key`.
+const DEQUEUE_RELIABLE_SCRIPT: &str = r#"
+local raw = redis.call('LMOVE', KEYS[1], KEYS[2], 'RIGHT', 'LEFT')
+if not raw then
+    return false
+end
+local item = cjson.decode(raw)
+redis.call('SADD', KEYS[3], ARGV[1])
+redis.call('SET', 'processing:' .. ARGV[1] .. ':' .. item.id, ARGV[2])
+return raw
+"#;
+
+/// A reliably-dequeued item's in-flight handle: which worker's processing
+/// list it lives on and its exact serialized form, so `ack` can `LREM` the
+/// same entry back out.
+#[derive(Debug, Clone)]
+pub struct InFlight {
+    pub worker: String,
+    pub id: String,
+    raw: String,
+}
+
 pub struct QueueHandler {
-    redis: Arc<redis::Client>,
+    redis: Arc<ConnectionManager>,
     processing_queue: String,
     retry_queue: String,
     max_retries: i32,
+    mode: QueueMode,
 }
 
 impl QueueHandler {
-    pub fn new(
+    pub async fn new(
         redis_client: redis::Client,
         processing_queue: String,
         retry_queue: String,
         max_retries: i32,
-    ) -> Self {
-        Self {
-            redis: Arc::new(redis_client),
+        mode: QueueMode,
+    ) -> Result<Self> {
+        let redis = Arc::new(ConnectionManager::new(redis_client).await?);
+        Ok(Self {
+            redis,
             processing_queue,
             retry_queue,
             max_retries,
-        }
+            mode,
+        })
     }
 
     #[instrument(skip(self, item))]
     pub async fn enqueue(&self, item: QueueItem) -> Result<()> {
+        let start = Instant::now();
         info!("Enqueueing item: {}", item.id);
-        let mut conn = self.redis.get_async_connection().await?;
-        
+        let mut conn = (*self.redis).clone();
         let serialized = serde_json::to_string(&item)?;
-        conn.lpush(&self.processing_queue, serialized).await?;
-        
+
+        match self.mode {
+            QueueMode::List => {
+                conn.lpush(&self.processing_queue, serialized).await?;
+            }
+            QueueMode::Priority => {
+                // Higher priority sorts first (more negative score); the
+                // monotonic sequence number breaks ties in FIFO order.
+                let seq: i64 = conn.incr(Self::seq_key(&self.processing_queue), 1).await?;
+                let score = -(item.priority as f64) * 1e12 + seq as f64;
+                conn.zadd(&self.processing_queue, serialized, score).await?;
+            }
+        }
+
+        histogram!("queue_enqueue_duration_seconds").record(start.elapsed().as_secs_f64());
         info!("Successfully enqueued item: {}", item.id);
         Ok(())
     }
 
     #[instrument(skip(self))]
     pub async fn dequeue(&self) -> Result<Option<QueueItem>> {
-        let mut conn = self.redis.get_async_connection().await?;
-        
-        if let Some(data) = conn.rpop::<_, Option<String>>(&self.processing_queue).await? {
+        let start = Instant::now();
+        let mut conn = (*self.redis).clone();
+
+        let data = match self.mode {
+            QueueMode::List => conn.rpop::<_, Option<String>>(&self.processing_queue).await?,
+            QueueMode::Priority => {
+                let popped: Vec<(String, f64)> =
+                    conn.zpopmin(&self.processing_queue, 1).await?;
+                popped.into_iter().next().map(|(member, _score)| member)
+            }
+        };
+
+        histogram!("queue_dequeue_duration_seconds").record(start.elapsed().as_secs_f64());
+
+        if let Some(data) = data {
             info!("Dequeued item from processing queue");
             let item: QueueItem = serde_json::from_str(&data)?;
             Ok(Some(item))
@@ -60,6 +142,240 @@ impl QueueHandler {
         }
     }
 
+    fn seq_key(processing_queue: &str) -> String {
+        format!("{}:seq", processing_queue)
+    }
+
+    /// Enqueue many items with a fixed number of Redis round-trips: one
+    /// multi-value `LPUSH` in `List` mode, or two in `Priority` mode (one
+    /// sequence-reserving `INCRBY`, then one multi-member `ZADD`). A
+    /// serialization failure only fails that item's slot in the result —
+    /// every other item still makes
+    /// it onto the queue.
+    #[instrument(skip(self, items))]
+    pub async fn enqueue_batch(&self, items: Vec<QueueItem>) -> Result<Vec<Result<()>>> {
+        let start = Instant::now();
+        let mut results = Vec::with_capacity(items.len());
+        let mut serialized = Vec::with_capacity(items.len());
+
+        for item in &items {
+            match serde_json::to_string(item) {
+                Ok(data) => {
+                    serialized.push((item.priority, data));
+                    results.push(Ok(()));
+                }
+                Err(e) => results.push(Err(ProcessingError::Queue(format!(
+                    "failed to serialize item {}: {}",
+                    item.id, e
+                )))),
+            }
+        }
+
+        if !serialized.is_empty() {
+            let mut conn = (*self.redis).clone();
+
+            match self.mode {
+                QueueMode::List => {
+                    let values: Vec<&str> = serialized.iter().map(|(_, data)| data.as_str()).collect();
+                    conn.lpush(&self.processing_queue, values).await?;
+                }
+                QueueMode::Priority => {
+                    let n = serialized.len() as i64;
+                    let last_seq: i64 = conn
+                        .incr(Self::seq_key(&self.processing_queue), n)
+                        .await?;
+                    let base_seq = last_seq - n + 1;
+
+                    let members: Vec<(f64, &str)> = serialized
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, (priority, data))| {
+                            let seq = base_seq + offset as i64;
+                            let score = -(*priority as f64) * 1e12 + seq as f64;
+                            (score, data.as_str())
+                        })
+                        .collect();
+                    conn.zadd_multiple(&self.processing_queue, &members).await?;
+                }
+            }
+        }
+
+        histogram!("queue_enqueue_duration_seconds").record(start.elapsed().as_secs_f64());
+        info!("Batch-enqueued {} items ({} serialized)", results.len(), serialized.len());
+        Ok(results)
+    }
+
+    /// Dequeue up to `max` items in a single Redis round-trip: a pipeline of
+    /// `max` `RPOP`s in `List` mode, or one multi-item `ZPOPMIN` in
+    /// `Priority` mode. A deserialization failure only fails that item's
+    /// slot in the result; it doesn't take down the rest of the batch.
+    #[instrument(skip(self))]
+    pub async fn dequeue_batch(&self, max: usize) -> Result<Vec<Result<QueueItem>>> {
+        let start = Instant::now();
+        let mut conn = (*self.redis).clone();
+
+        let raw: Vec<String> = match self.mode {
+            QueueMode::List => {
+                let mut pipeline = redis::pipe();
+                for _ in 0..max {
+                    pipeline.rpop(&self.processing_queue, None);
+                }
+                let popped: Vec<Option<String>> = pipeline.query_async(&mut conn).await?;
+                popped.into_iter().flatten().collect()
+            }
+            QueueMode::Priority => {
+                let popped: Vec<(String, f64)> =
+                    conn.zpopmin(&self.processing_queue, max as isize).await?;
+                popped.into_iter().map(|(member, _score)| member).collect()
+            }
+        };
+
+        let items: Vec<Result<QueueItem>> = raw
+            .into_iter()
+            .map(|data| {
+                serde_json::from_str::<QueueItem>(&data)
+                    .map_err(|e| ProcessingError::Queue(format!("failed to deserialize item: {}", e)))
+            })
+            .collect();
+
+        histogram!("queue_dequeue_duration_seconds").record(start.elapsed().as_secs_f64());
+        info!("Batch-dequeued {} items", items.len());
+        Ok(items)
+    }
+
+    /// At-least-once dequeue: atomically move an item from the main queue
+    /// onto `worker`'s own processing list (`LMOVE`, the modern equivalent
+    /// of `RPOPLPUSH`) and record when it landed there, so a worker that
+    /// dies mid-processing doesn't lose the item the way a plain `RPOP`
+    /// would — `reap_stale` can recover it once its visibility timeout
+    /// elapses. The `LMOVE` and the bookkeeping that lets `reap_stale` find
+    /// the item again (`SADD` into `PROCESSING_WORKERS_SET`, `SET` of its
+    /// timestamp key) run as one `EVAL` so a crash can never land between
+    /// them — otherwise the item would sit on the processing list with no
+    /// timestamp key, and `reap_stale` skips timestamp-less entries forever.
+    #[instrument(skip(self))]
+    pub async fn dequeue_reliable(&self, worker: &str) -> Result<Option<(QueueItem, InFlight)>> {
+        if self.mode != QueueMode::List {
+            return Err(ProcessingError::Queue(
+                "reliable dequeue requires QueueMode::List (LMOVE has no sorted-set equivalent)"
+                    .to_string(),
+            ));
+        }
+
+        let mut conn = (*self.redis).clone();
+        let processing_list = Self::processing_list_key(worker);
+
+        let raw: Option<String> = redis::Script::new(DEQUEUE_RELIABLE_SCRIPT)
+            .key(&self.processing_queue)
+            .key(&processing_list)
+            .key(PROCESSING_WORKERS_SET)
+            .arg(worker)
+            .arg(Self::now_secs())
+            .invoke_async(&mut conn)
+            .await?;
+
+        let Some(raw) = raw else {
+            return Ok(None);
+        };
+
+        let item: QueueItem = serde_json::from_str(&raw)?;
+        let id = item.id.clone();
+
+        info!("Reliably dequeued item {} onto {}'s processing list", id, worker);
+        Ok(Some((
+            item,
+            InFlight {
+                worker: worker.to_string(),
+                id,
+                raw,
+            },
+        )))
+    }
+
+    /// Acknowledge successful processing of a reliably-dequeued item:
+    /// remove it from its worker's processing list and clear its
+    /// visibility-timeout timestamp.
+    #[instrument(skip(self, handle))]
+    pub async fn ack(&self, handle: InFlight) -> Result<()> {
+        let mut conn = (*self.redis).clone();
+        let processing_list = Self::processing_list_key(&handle.worker);
+
+        conn.lrem(&processing_list, 1, &handle.raw).await?;
+        conn.del(Self::timestamp_key(&handle.worker, &handle.id))
+            .await?;
+
+        info!("Acknowledged item {}", handle.id);
+        Ok(())
+    }
+
+    /// Scan every known worker's processing list for items whose dequeue
+    /// timestamp exceeds `timeout`, and recover them through the normal
+    /// `retry` path (which re-enqueues with `retry_count` incremented, or
+    /// routes to the dead-letter queue past `max_retries`). Returns how
+    /// many items were recovered.
+    #[instrument(skip(self))]
+    pub async fn reap_stale(&self, timeout: Duration) -> Result<usize> {
+        let mut conn = (*self.redis).clone();
+        let workers: Vec<String> = conn.smembers(PROCESSING_WORKERS_SET).await?;
+        let now = Self::now_secs();
+        let mut reaped = 0;
+
+        for worker in workers {
+            let processing_list = Self::processing_list_key(&worker);
+            let items: Vec<String> = conn.lrange(&processing_list, 0, -1).await?;
+
+            for raw in items {
+                let item: QueueItem = match serde_json::from_str(&raw) {
+                    Ok(item) => item,
+                    Err(_) => continue,
+                };
+
+                let ts_key = Self::timestamp_key(&worker, &item.id);
+                let dequeued_at: Option<i64> = conn.get(&ts_key).await?;
+                let Some(dequeued_at) = dequeued_at else {
+                    continue;
+                };
+
+                if now - dequeued_at < timeout.as_secs() as i64 {
+                    continue;
+                }
+
+                // Another reaper (or the worker itself) may have acked this
+                // exact entry concurrently; only recover it if we actually
+                // removed it from the processing list ourselves.
+                let removed: i32 = conn.lrem(&processing_list, 1, &raw).await?;
+                if removed == 0 {
+                    continue;
+                }
+                conn.del(&ts_key).await?;
+
+                error!(
+                    "Item {} exceeded visibility timeout on worker {}, recovering",
+                    item.id, worker
+                );
+                self.retry(item).await?;
+                reaped += 1;
+            }
+        }
+
+        Ok(reaped)
+    }
+
+    fn processing_list_key(worker: &str) -> String {
+        format!("processing:{}", worker)
+    }
+
+    fn timestamp_key(worker: &str, id: &str) -> String {
+        format!("processing:{}:{}", worker, id)
+    }
+
+    fn now_secs() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64
+    }
+
     #[instrument(skip(self, item))]
     pub async fn retry(&self, mut item: QueueItem) -> Result<()> {
         item.retry_count += 1;
@@ -71,7 +387,7 @@ impl QueueHandler {
         }
         
         info!("Retrying item: {} (attempt {})", item.id, item.retry_count);
-        let mut conn = self.redis.get_async_connection().await?;
+        let mut conn = (*self.redis).clone();
         
         let serialized = serde_json::to_string(&item)?;
         conn.lpush(&self.retry_queue, serialized).await?;
@@ -82,38 +398,83 @@ impl QueueHandler {
     #[instrument(skip(self, item))]
     async fn move_to_dead_letter(&self, item: &QueueItem) -> Result<()> {
         info!("Moving item {} to dead letter queue", item.id);
-        let mut conn = self.redis.get_async_connection().await?;
-        
+        let mut conn = (*self.redis).clone();
+
         let serialized = serde_json::to_string(&item)?;
-        conn.lpush("dead_letter_queue", serialized).await?;
-        
+        conn.lpush(DEAD_LETTER_QUEUE, serialized).await?;
+
         Ok(())
     }
 
     #[instrument(skip(self))]
     pub async fn process_retries(&self) -> Result<()> {
-        let mut conn = self.redis.get_async_connection().await?;
-        
-        while let Some(data) = conn.rpop::<_, Option<String>>(&self.retry_queue).await? {
-            info!("Processing retry item");
-            let item: QueueItem = serde_json::from_str(&data)?;
-            
-            // Move back to main queue with increased priority
-            let mut item = item;
-            item.priority += 1;
-            self.enqueue(item).await?;
+        loop {
+            match self.process_one_retry().await? {
+                WorkerState::Busy => continue,
+                WorkerState::Idle | WorkerState::Done => break,
+            }
         }
-        
+
         Ok(())
     }
 
+    /// Pop one item off the retry queue and move it back to the main
+    /// queue with increased priority. Returns `Idle` once the retry queue
+    /// is empty, so this also serves as the step function for
+    /// `RetryQueueWorker`.
+    async fn process_one_retry(&self) -> Result<WorkerState> {
+        let mut conn = (*self.redis).clone();
+
+        let Some(data) = conn.rpop::<_, Option<String>>(&self.retry_queue).await? else {
+            return Ok(WorkerState::Idle);
+        };
+
+        info!("Processing retry item");
+        let mut item: QueueItem = serde_json::from_str(&data)?;
+        item.priority += 1;
+        self.enqueue(item).await?;
+
+        Ok(WorkerState::Busy)
+    }
+
+    /// Query the main and retry queue lengths, refreshing the
+    /// `queue_depth` gauges (main/retry/dead_letter) along the way so a
+    /// Prometheus scrape always reflects the last time this was called.
     #[instrument(skip(self))]
     pub async fn get_queue_length(&self) -> Result<(i64, i64)> {
-        let mut conn = self.redis.get_async_connection().await?;
-        
-        let processing: i64 = conn.llen(&self.processing_queue).await?;
+        let mut conn = (*self.redis).clone();
+
+        let processing: i64 = match self.mode {
+            QueueMode::List => conn.llen(&self.processing_queue).await?,
+            QueueMode::Priority => conn.zcard(&self.processing_queue).await?,
+        };
         let retry: i64 = conn.llen(&self.retry_queue).await?;
-        
+        let dead_letter: i64 = conn.llen(DEAD_LETTER_QUEUE).await?;
+
+        gauge!("queue_depth", "queue" => "main").set(processing as f64);
+        gauge!("queue_depth", "queue" => "retry").set(retry as f64);
+        gauge!("queue_depth", "queue" => "dead_letter").set(dead_letter as f64);
+
         Ok((processing, retry))
     }
+}
+
+/// Drives a `QueueHandler`'s retry queue via `WorkerManager`, so it gets
+/// idle backoff, throttling, and cooperative shutdown instead of a
+/// cron-style call to `process_retries`.
+pub struct RetryQueueWorker {
+    handler: Arc<QueueHandler>,
+}
+
+impl RetryQueueWorker {
+    pub fn new(handler: Arc<QueueHandler>) -> Self {
+        Self { handler }
+    }
+}
+
+#[async_trait]
+impl Worker for RetryQueueWorker {
+    async fn work(&mut self) -> Result<WorkerState> {
+        self.handler.process_one_retry().await
+    }
 }
\ No newline at end of file
@@ -1,4 +1,8 @@
 use crate::error::{ProcessingError, Result};
+use crate::worker::{Worker, WorkerState};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use tokio;
 use std::sync::Arc;
@@ -7,7 +11,7 @@ use chrono::{DateTime, Utc};
 use tracing::{info, error, instrument};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResearchSession {
     pub id: String,
     pub topic: String,
@@ -19,7 +23,7 @@ pub struct ResearchSession {
     pub completion_percentage: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ResearchStatus {
     Initializing,
     SearchingPapers,
@@ -29,6 +33,31 @@ pub enum ResearchStatus {
     Error,
 }
 
+impl ResearchStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ResearchStatus::Initializing => "initializing",
+            ResearchStatus::SearchingPapers => "searching_papers",
+            ResearchStatus::ProcessingDocuments => "processing_documents",
+            ResearchStatus::BuildingKnowledge => "building_knowledge",
+            ResearchStatus::Completed => "completed",
+            ResearchStatus::Error => "error",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "initializing" => ResearchStatus::Initializing,
+            "searching_papers" => ResearchStatus::SearchingPapers,
+            "processing_documents" => ResearchStatus::ProcessingDocuments,
+            "building_knowledge" => ResearchStatus::BuildingKnowledge,
+            "completed" => ResearchStatus::Completed,
+            "error" => ResearchStatus::Error,
+            _ => return None,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResearchProgress {
     pub papers_total: i32,
@@ -38,18 +67,107 @@ pub struct ResearchProgress {
     pub last_processed: Option<String>,
 }
 
+/// A page of sessions returned by `list_sessions`, plus the total count
+/// matching the filter so a caller can render pagination controls.
+#[derive(Debug, Clone)]
+pub struct SessionPage {
+    pub sessions: Vec<ResearchSession>,
+    pub total: usize,
+}
+
+/// Sorted set of session ids, scored by `created_at` (unix seconds), so
+/// sessions can be listed newest-first and aged out with `ZRANGEBYSCORE`.
+const SESSION_INDEX: &str = "research:index";
+
 pub struct ResearchManager {
-    redis_client: Arc<redis::Client>,
-    active_sessions: HashMap<String, ResearchSession>,
+    redis: Arc<ConnectionManager>,
 }
 
 impl ResearchManager {
-    pub fn new(redis_url: &str) -> Result<Self> {
-        info!("Initializing ResearchManager");
+    /// Connect to Redis. Sessions already live in
+    /// `research:index`/`research:{id}`, so there's nothing to rehydrate
+    /// into memory — `get_research_status`/`list_sessions` read Redis
+    /// directly and survive a process restart with no in-process cache.
+    pub async fn new(redis_url: &str) -> Result<Self> {
+        info!("Initializing ResearchManager with Redis URL: {}", redis_url);
         let client = redis::Client::open(redis_url)?;
-        Ok(Self {
-            redis_client: Arc::new(client),
-            active_sessions: HashMap::new(),
+        let redis = Arc::new(ConnectionManager::new(client).await?);
+
+        Ok(Self { redis })
+    }
+
+    fn session_key(session_id: &str) -> String {
+        format!("research:{}", session_id)
+    }
+
+    fn progress_key(session_id: &str) -> String {
+        format!("research:{}:progress", session_id)
+    }
+
+    /// Zeroed progress for a session that's been created but not yet
+    /// picked up by a `ResearchQueueWorker`, so `get_research_status` has
+    /// something to read the moment a session exists rather than only
+    /// once it reaches `SearchingPapers`.
+    fn initializing_progress() -> ResearchProgress {
+        ResearchProgress {
+            papers_total: 0,
+            papers_processed: 0,
+            papers_failed: 0,
+            current_phase: "queued".to_string(),
+            last_processed: None,
+        }
+    }
+
+    /// Write a session to its Redis hash and index it in `research:index`
+    /// scored by `created_at`.
+    async fn persist_session(&self, session: &ResearchSession) -> Result<()> {
+        let mut conn = (*self.redis).clone();
+        let key = Self::session_key(&session.id);
+
+        conn.hset_multiple(
+            &key,
+            &[
+                ("id", session.id.clone()),
+                ("topic", session.topic.clone()),
+                ("status", session.status.as_str().to_string()),
+                ("created_at", session.created_at.to_rfc3339()),
+                ("updated_at", session.updated_at.to_rfc3339()),
+                ("papers_found", session.papers_found.to_string()),
+                ("papers_processed", session.papers_processed.to_string()),
+                ("completion_percentage", session.completion_percentage.to_string()),
+            ],
+        )
+        .await?;
+        conn.zadd(SESSION_INDEX, &session.id, session.created_at.timestamp() as f64)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn load_session(&self, session_id: &str) -> Result<Option<ResearchSession>> {
+        let mut conn = (*self.redis).clone();
+        let fields: HashMap<String, String> = conn.hgetall(Self::session_key(session_id)).await?;
+
+        if fields.is_empty() {
+            return Ok(None);
+        }
+        Ok(Self::session_from_fields(&fields))
+    }
+
+    fn session_from_fields(fields: &HashMap<String, String>) -> Option<ResearchSession> {
+        Some(ResearchSession {
+            id: fields.get("id")?.clone(),
+            topic: fields.get("topic")?.clone(),
+            status: ResearchStatus::parse(fields.get("status")?)?,
+            created_at: DateTime::parse_from_rfc3339(fields.get("created_at")?)
+                .ok()?
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(fields.get("updated_at")?)
+                .ok()?
+                .with_timezone(&Utc),
+            papers_found: fields.get("papers_found")?.parse().ok()?,
+            papers_processed: fields.get("papers_processed")?.parse().ok()?,
+            completion_percentage: fields.get("completion_percentage")?.parse().ok()?,
         })
     }
 
@@ -69,75 +187,238 @@ impl ResearchManager {
             completion_percentage: 0.0,
         };
         
-        self.active_sessions.insert(session_id.clone(), session);
-        self.queue_research_task(&session_id).await?;
-        
+        self.persist_session(&session).await?;
+        self.update_progress(&session_id, &Self::initializing_progress()).await?;
+        self.queue_research_task(&session).await?;
+
         info!("Research session started: {}", session_id);
         Ok(session_id)
     }
 
+    /// Create and queue many sessions in one pipelined Redis round-trip —
+    /// the per-session `HSET`, index `ZADD`, and `research_queue` `LPUSH`
+    /// for every topic, all sent together. A session whose serialization
+    /// fails is skipped (and reported as an `Err` in its slot) without
+    /// affecting the rest of the batch; the others are still persisted and
+    /// queued.
+    #[instrument(skip(self, topics))]
+    pub async fn start_research_batch(&mut self, topics: Vec<String>) -> Result<Vec<Result<String>>> {
+        info!("Starting {} research sessions in a batch", topics.len());
+
+        let mut pipeline = redis::pipe();
+        let mut results = Vec::with_capacity(topics.len());
+        let mut queued_any = false;
+
+        for topic in topics {
+            let session_id = Uuid::new_v4().to_string();
+            let session = ResearchSession {
+                id: session_id.clone(),
+                topic,
+                status: ResearchStatus::Initializing,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                papers_found: 0,
+                papers_processed: 0,
+                completion_percentage: 0.0,
+            };
+
+            let serialized = match serde_json::to_string(&session) {
+                Ok(data) => data,
+                Err(e) => {
+                    results.push(Err(ProcessingError::System(format!(
+                        "failed to serialize session {}: {}",
+                        session_id, e
+                    ))));
+                    continue;
+                }
+            };
+            let serialized_progress = match serde_json::to_string(&Self::initializing_progress()) {
+                Ok(data) => data,
+                Err(e) => {
+                    results.push(Err(ProcessingError::System(format!(
+                        "failed to serialize progress for session {}: {}",
+                        session_id, e
+                    ))));
+                    continue;
+                }
+            };
+
+            let key = Self::session_key(&session_id);
+            pipeline
+                .hset_multiple(
+                    &key,
+                    &[
+                        ("id", session.id.clone()),
+                        ("topic", session.topic.clone()),
+                        ("status", session.status.as_str().to_string()),
+                        ("created_at", session.created_at.to_rfc3339()),
+                        ("updated_at", session.updated_at.to_rfc3339()),
+                        ("papers_found", session.papers_found.to_string()),
+                        ("papers_processed", session.papers_processed.to_string()),
+                        ("completion_percentage", session.completion_percentage.to_string()),
+                    ],
+                )
+                .ignore()
+                .zadd(SESSION_INDEX, &session_id, session.created_at.timestamp() as f64)
+                .ignore()
+                .set(Self::progress_key(&session_id), serialized_progress)
+                .ignore()
+                .lpush("research_queue", serialized)
+                .ignore();
+
+            results.push(Ok(session_id));
+            queued_any = true;
+        }
+
+        if queued_any {
+            let mut conn = (*self.redis).clone();
+            pipeline.query_async::<_, ()>(&mut conn).await?;
+        }
+
+        info!(
+            "Batch-started {} research sessions ({} queued)",
+            results.len(),
+            results.iter().filter(|r| r.is_ok()).count()
+        );
+        Ok(results)
+    }
+
     #[instrument(skip(self))]
     pub async fn get_research_status(&self, session_id: &str) -> Result<ResearchProgress> {
         info!("Checking status for session: {}", session_id);
-        
-        let mut conn = self.redis_client.get_async_connection().await?;
-        let progress: String = conn.get(format!("research:{}:progress", session_id)).await?;
-        
+
+        let mut conn = (*self.redis).clone();
+        let progress: String = conn.get(Self::progress_key(session_id)).await?;
+
         serde_json::from_str(&progress)
             .map_err(|e| ProcessingError::System(format!("Failed to parse progress: {}", e)))
     }
 
-    #[instrument(skip(self, session_id))]
-    async fn queue_research_task(&self, session_id: &str) -> Result<()> {
-        info!("Queueing research task for session: {}", session_id);
-        
-        let mut conn = self.redis_client.get_async_connection().await?;
-        
+    /// Persist `progress` for `session_id`, so `get_research_status` has
+    /// something to read.
+    #[instrument(skip(self, progress))]
+    async fn update_progress(&self, session_id: &str, progress: &ResearchProgress) -> Result<()> {
+        let mut conn = (*self.redis).clone();
+        conn.set(Self::progress_key(session_id), serde_json::to_string(progress)?)
+            .await?;
+        Ok(())
+    }
+
+    /// List sessions newest-first, optionally filtered by `status`, with
+    /// `offset`/`limit` pagination. `SessionPage::total` is the count that
+    /// matched the filter before pagination was applied.
+    #[instrument(skip(self))]
+    pub async fn list_sessions(
+        &self,
+        status: Option<ResearchStatus>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SessionPage> {
+        let mut conn = (*self.redis).clone();
+        let ids: Vec<String> = conn.zrevrange(SESSION_INDEX, 0, -1).await?;
+
+        let mut matching = Vec::new();
+        for id in ids {
+            if let Some(session) = self.load_session(&id).await? {
+                if status.map_or(true, |s| s == session.status) {
+                    matching.push(session);
+                }
+            }
+        }
+
+        let total = matching.len();
+        let sessions = matching.into_iter().skip(offset).take(limit).collect();
+
+        Ok(SessionPage { sessions, total })
+    }
+
+    #[instrument(skip(self, session))]
+    async fn queue_research_task(&self, session: &ResearchSession) -> Result<()> {
+        info!("Queueing research task for session: {}", session.id);
+
+        let mut conn = (*self.redis).clone();
+
         // Add to processing queue
-        conn.lpush(
-            "research_queue",
-            serde_json::to_string(&self.active_sessions.get(session_id).unwrap())?
-        ).await?;
-        
+        conn.lpush("research_queue", serde_json::to_string(session)?).await?;
+
         Ok(())
     }
 
     #[instrument(skip(self))]
     pub async fn process_research_queue(&self) -> Result<()> {
         info!("Starting research queue processing");
-        
-        let mut conn = self.redis_client.get_async_connection().await?;
-        
-        while let Some(session_data) = conn.rpop::<_, Option<String>>("research_queue").await? {
-            let session: ResearchSession = serde_json::from_str(&session_data)?;
-            
-            match self.process_research_session(&session).await {
-                Ok(_) => {
-                    info!("Successfully processed research session: {}", session.id);
-                    self.update_session_status(&session.id, ResearchStatus::Completed).await?;
-                },
-                Err(e) => {
-                    error!("Failed to process research session {}: {}", session.id, e);
-                    self.update_session_status(&session.id, ResearchStatus::Error).await?;
-                }
+
+        loop {
+            match self.process_one_from_queue().await? {
+                WorkerState::Busy => continue,
+                WorkerState::Idle | WorkerState::Done => break,
             }
         }
-        
+
         Ok(())
     }
 
+    /// Pop one session off `research_queue` and run it through
+    /// `process_research_session`. Returns `Idle` once the queue is
+    /// empty, so this also serves as the step function for
+    /// `ResearchQueueWorker`.
+    async fn process_one_from_queue(&self) -> Result<WorkerState> {
+        let mut conn = (*self.redis).clone();
+
+        let Some(session_data) = conn.rpop::<_, Option<String>>("research_queue").await? else {
+            return Ok(WorkerState::Idle);
+        };
+
+        let session: ResearchSession = serde_json::from_str(&session_data)?;
+
+        match self.process_research_session(&session).await {
+            Ok(_) => {
+                info!("Successfully processed research session: {}", session.id);
+                self.update_session_status(&session.id, ResearchStatus::Completed).await?;
+            }
+            Err(e) => {
+                error!("Failed to process research session {}: {}", session.id, e);
+                self.update_session_status(&session.id, ResearchStatus::Error).await?;
+            }
+        }
+
+        Ok(WorkerState::Busy)
+    }
+
     #[instrument(skip(self, session))]
     async fn process_research_session(&self, session: &ResearchSession) -> Result<()> {
         info!("Processing research session: {}", session.id);
-        
-        // Update status to searching
+
         self.update_session_status(&session.id, ResearchStatus::SearchingPapers).await?;
-        
-        // Process phases
+        self.update_progress(&session.id, &ResearchProgress {
+            papers_total: 0,
+            papers_processed: 0,
+            papers_failed: 0,
+            current_phase: "searching_papers".to_string(),
+            last_processed: None,
+        }).await?;
         self.search_papers(session).await?;
+
+        self.update_session_status(&session.id, ResearchStatus::ProcessingDocuments).await?;
+        self.update_progress(&session.id, &ResearchProgress {
+            papers_total: session.papers_found,
+            papers_processed: 0,
+            papers_failed: 0,
+            current_phase: "processing_documents".to_string(),
+            last_processed: None,
+        }).await?;
         self.process_documents(session).await?;
+
+        self.update_session_status(&session.id, ResearchStatus::BuildingKnowledge).await?;
+        self.update_progress(&session.id, &ResearchProgress {
+            papers_total: session.papers_found,
+            papers_processed: session.papers_processed,
+            papers_failed: 0,
+            current_phase: "building_knowledge".to_string(),
+            last_processed: None,
+        }).await?;
         self.build_knowledge_base(session).await?;
-        
+
         Ok(())
     }
 
@@ -162,27 +443,64 @@ impl ResearchManager {
     #[instrument(skip(self))]
     async fn update_session_status(&self, session_id: &str, status: ResearchStatus) -> Result<()> {
         info!("Updating session {} status to {:?}", session_id, status);
-        
-        let mut conn = self.redis_client.get_async_connection().await?;
-        
-        // Update status in Redis
-        conn.set(
-            format!("research:{}:status", session_id),
-            serde_json::to_string(&status)?
-        ).await?;
-        
+
+        let mut conn = (*self.redis).clone();
+        let key = Self::session_key(session_id);
+
+        conn.hset_multiple(
+            &key,
+            &[
+                ("status", status.as_str().to_string()),
+                ("updated_at", Utc::now().to_rfc3339()),
+            ],
+        )
+        .await?;
+
         Ok(())
     }
 
+    /// Delete every session (and its progress key) whose `created_at` is
+    /// older than `age_hours`, via `ZRANGEBYSCORE` against `research:index`.
+    /// Returns how many sessions were removed.
     #[instrument(skip(self))]
     pub async fn cleanup_old_sessions(&self, age_hours: i64) -> Result<i32> {
-        info!("Cleaning up old research sessions");
-        
-        let mut conn = self.redis_client.get_async_connection().await?;
-        let mut cleaned = 0;
-        
-        // Implement cleanup logic
-        
-        Ok(cleaned)
+        info!("Cleaning up research sessions older than {} hours", age_hours);
+
+        let mut conn = (*self.redis).clone();
+        let cutoff = (Utc::now() - chrono::Duration::hours(age_hours)).timestamp();
+
+        let stale_ids: Vec<String> = conn.zrangebyscore(SESSION_INDEX, "-inf", cutoff).await?;
+
+        for id in &stale_ids {
+            conn.del(Self::session_key(id)).await?;
+            conn.del(Self::progress_key(id)).await?;
+        }
+
+        if !stale_ids.is_empty() {
+            conn.zrembyscore(SESSION_INDEX, "-inf", cutoff).await?;
+        }
+
+        info!("Cleaned up {} stale research sessions", stale_ids.len());
+        Ok(stale_ids.len() as i32)
+    }
+}
+
+/// Drives a `ResearchManager`'s `research_queue` via `WorkerManager`, so
+/// the pipeline gets concurrency control, idle backoff, and cooperative
+/// shutdown instead of a cron-style call to `process_research_queue`.
+pub struct ResearchQueueWorker {
+    manager: Arc<ResearchManager>,
+}
+
+impl ResearchQueueWorker {
+    pub fn new(manager: Arc<ResearchManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl Worker for ResearchQueueWorker {
+    async fn work(&mut self) -> Result<WorkerState> {
+        self.manager.process_one_from_queue().await
     }
 }
\ No newline at end of file
@@ -0,0 +1,360 @@
+use crate::ProcessedDocument;
+use std::collections::{HashMap, HashSet};
+
+const STOP_WORDS: [&str; 25] = [
+    "the", "a", "an", "and", "or", "but", "in", "on", "at", "to", "for", "of", "with", "by", "is",
+    "are", "was", "were", "be", "been", "being", "this", "that", "these", "those",
+];
+
+/// Whether `word` is common enough to be noise in term-frequency contexts
+/// (trending terms, keyword extraction) rather than a signal of what a
+/// document is actually about.
+pub fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.contains(&word)
+}
+
+/// A single fuzzy/exact/phrase leaf match, as produced by the query parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryKind {
+    Exact(String),
+    Tolerant(String),
+    Phrase(Vec<String>),
+}
+
+/// A boolean tree of query operations, evaluated against the inverted index.
+#[derive(Debug, Clone)]
+pub enum Operation {
+    And(Vec<Operation>),
+    Or(Vec<Operation>),
+    Query { prefix: bool, kind: QueryKind },
+}
+
+/// One hit against the index: the chunks of a document that matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub doc_id: String,
+    pub chunk_indices: Vec<usize>,
+    pub matched_terms: usize,
+    /// Sum of gaps between sorted term positions in the tightest-clustered
+    /// matching chunk; lower is better. Every occurrence counts, so a
+    /// one-word query that occurs twice in a chunk still has a gap; `0`
+    /// means the chunk has only one distinct matched position.
+    pub proximity: usize,
+}
+
+/// Posting list entry: chunk index -> word-offset positions of the term
+/// within that chunk, so callers can measure how tightly matched terms
+/// cluster together.
+type Postings = HashMap<usize, Vec<usize>>;
+
+/// Per-doc match state while evaluating a query subtree: for each chunk
+/// that matched, the positions of every matched term within it (merged
+/// across terms so proximity can be computed), plus a running count of
+/// distinct query terms the document matched.
+type DocMatches = HashMap<String, (HashMap<usize, Vec<usize>>, usize)>;
+
+/// Max edit distance allowed for a tolerant match, scaled by term length.
+fn max_distance_for(term: &str) -> usize {
+    match term.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Row-based Levenshtein automaton. Instead of building an explicit DFA table
+/// up front, each step folds the previous edit-distance row into the next one,
+/// which is equivalent to a DFA walk but avoids pre-computing all states.
+struct LevenshteinAutomaton<'a> {
+    term: &'a [char],
+    max_distance: usize,
+}
+
+impl<'a> LevenshteinAutomaton<'a> {
+    fn new(term: &'a [char], max_distance: usize) -> Self {
+        Self { term, max_distance }
+    }
+
+    /// Returns true if `candidate` is within `max_distance` edits of the term,
+    /// or (when `prefix` is set) within `max_distance` edits of some prefix of
+    /// `candidate` that covers the whole term.
+    fn accepts(&self, candidate: &str, prefix: bool) -> bool {
+        let candidate: Vec<char> = candidate.chars().collect();
+        let mut row: Vec<usize> = (0..=self.term.len()).collect();
+
+        for (i, &c) in candidate.iter().enumerate() {
+            let mut next_row = vec![0usize; self.term.len() + 1];
+            next_row[0] = row[0] + 1;
+
+            for (j, &t) in self.term.iter().enumerate() {
+                let cost = if c == t { 0 } else { 1 };
+                next_row[j + 1] = (row[j] + cost)
+                    .min(row[j + 1] + 1)
+                    .min(next_row[j] + 1);
+            }
+
+            // Prune rows that can no longer reach an accepting state.
+            if *next_row.iter().min().unwrap() > self.max_distance {
+                return false;
+            }
+
+            row = next_row;
+
+            if prefix && i + 1 >= self.term.len() && row[self.term.len()] <= self.max_distance {
+                return true;
+            }
+        }
+
+        row[self.term.len()] <= self.max_distance
+    }
+}
+
+/// An inverted index over `TextChunk` content, answering typo-tolerant,
+/// prefix, and boolean queries the way a query-tree search engine would.
+#[derive(Default)]
+pub struct SearchIndex {
+    /// term -> doc_id -> chunk index -> positions of the term in that chunk
+    postings: HashMap<String, HashMap<String, Postings>>,
+    vocabulary: Vec<String>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ingest a processed document, indexing every word of every chunk along
+    /// with its position (word offset) within the chunk.
+    pub fn add_document(&mut self, doc: &ProcessedDocument) {
+        for chunk in &doc.chunks {
+            for (position, word) in tokenize(&chunk.content).into_iter().enumerate() {
+                let doc_postings = self
+                    .postings
+                    .entry(word.clone())
+                    .or_insert_with(HashMap::new)
+                    .entry(doc.doc_id.clone())
+                    .or_insert_with(HashMap::new);
+                doc_postings
+                    .entry(chunk.index)
+                    .or_insert_with(Vec::new)
+                    .push(position);
+
+                if !self.vocabulary.contains(&word) {
+                    self.vocabulary.push(word);
+                }
+            }
+        }
+        self.vocabulary.sort();
+    }
+
+    /// Parse a raw query string into an `Operation` tree: one `And` branch
+    /// per word, the last word treated as a prefix match.
+    pub fn parse_query(query: &str) -> Operation {
+        let words: Vec<String> = tokenize(query);
+        let last_index = words.len().saturating_sub(1);
+
+        let leaves: Vec<Operation> = words
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| Operation::Query {
+                prefix: i == last_index,
+                kind: QueryKind::Tolerant(word),
+            })
+            .collect();
+
+        if leaves.len() == 1 {
+            leaves.into_iter().next().unwrap()
+        } else {
+            Operation::And(leaves)
+        }
+    }
+
+    /// Evaluate a query string end to end and return ranked hits.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        self.evaluate(&Self::parse_query(query))
+    }
+
+    /// Evaluate an `Operation` tree, returning ranked matches.
+    pub fn evaluate(&self, op: &Operation) -> Vec<SearchHit> {
+        let matches = self.eval_op(op);
+
+        let mut hits: Vec<SearchHit> = matches
+            .into_iter()
+            .map(|(doc_id, (chunk_positions, matched_terms))| {
+                let proximity = chunk_positions
+                    .values()
+                    .map(|positions| Self::proximity(positions))
+                    .min()
+                    .unwrap_or(0);
+
+                let mut chunk_indices: Vec<usize> = chunk_positions.into_keys().collect();
+                chunk_indices.sort_unstable();
+                SearchHit {
+                    doc_id,
+                    chunk_indices,
+                    matched_terms,
+                    proximity,
+                }
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.matched_terms
+                .cmp(&a.matched_terms)
+                .then_with(|| a.proximity.cmp(&b.proximity))
+                .then_with(|| b.chunk_indices.len().cmp(&a.chunk_indices.len()))
+        });
+
+        hits
+    }
+
+    /// Sum of gaps between consecutive sorted term positions: the tighter
+    /// the matched terms cluster together in the chunk, the lower the
+    /// score. Fewer than two positions have nothing to measure, so they
+    /// score a perfect `0`.
+    fn proximity(positions: &[usize]) -> usize {
+        let mut sorted = positions.to_vec();
+        sorted.sort_unstable();
+        sorted.windows(2).map(|pair| pair[1] - pair[0]).sum()
+    }
+
+    /// Recursively evaluate a query node, returning per-doc matches: for
+    /// each chunk, the merged positions of every matched term, plus a
+    /// running count of distinct query terms each doc matched.
+    fn eval_op(&self, op: &Operation) -> DocMatches {
+        match op {
+            Operation::And(children) => {
+                let mut iter = children.iter().map(|c| self.eval_op(c));
+                let first = iter.next().unwrap_or_default();
+                iter.fold(first, |acc, next| {
+                    let mut merged = HashMap::new();
+                    for (doc_id, (chunks, count)) in acc {
+                        if let Some((other_chunks, other_count)) = next.get(&doc_id) {
+                            let mut combined = chunks;
+                            for (chunk_id, positions) in other_chunks {
+                                combined
+                                    .entry(*chunk_id)
+                                    .or_insert_with(Vec::new)
+                                    .extend(positions);
+                            }
+                            merged.insert(doc_id, (combined, count + other_count));
+                        }
+                    }
+                    merged
+                })
+            }
+            Operation::Or(children) => {
+                let mut merged: DocMatches = HashMap::new();
+                for child in children {
+                    for (doc_id, (chunks, count)) in self.eval_op(child) {
+                        let entry = merged.entry(doc_id).or_insert_with(|| (HashMap::new(), 0));
+                        for (chunk_id, positions) in chunks {
+                            entry.0.entry(chunk_id).or_insert_with(Vec::new).extend(positions);
+                        }
+                        // Alternatives stand in for the same query term, so
+                        // a doc matching several isn't counted twice.
+                        entry.1 = entry.1.max(count);
+                    }
+                }
+                merged
+            }
+            Operation::Query { prefix, kind } => self.eval_leaf(*prefix, kind),
+        }
+    }
+
+    fn eval_leaf(&self, prefix: bool, kind: &QueryKind) -> DocMatches {
+        let mut merged: DocMatches = HashMap::new();
+
+        match kind {
+            QueryKind::Exact(term) => {
+                if let Some(by_doc) = self.postings.get(term) {
+                    self.fold_term_postings(by_doc, &mut merged);
+                }
+            }
+            QueryKind::Tolerant(term) => {
+                let chars: Vec<char> = term.chars().collect();
+                let max_distance = max_distance_for(term);
+                let automaton = LevenshteinAutomaton::new(&chars, max_distance);
+
+                for candidate in &self.vocabulary {
+                    if automaton.accepts(candidate, prefix) {
+                        if let Some(by_doc) = self.postings.get(candidate) {
+                            self.fold_term_postings(by_doc, &mut merged);
+                        }
+                    }
+                }
+            }
+            QueryKind::Phrase(words) => {
+                // Require every word of the phrase to appear in the same chunk.
+                let mut per_word: Vec<HashMap<String, Postings>> = Vec::new();
+                for word in words {
+                    let doc_chunks = self.postings.get(word).cloned().unwrap_or_default();
+                    per_word.push(doc_chunks);
+                }
+
+                if let Some(first) = per_word.first() {
+                    for (doc_id, chunks) in first {
+                        let mut common_chunks: HashSet<usize> = chunks.keys().copied().collect();
+                        for other in &per_word[1..] {
+                            match other.get(doc_id) {
+                                Some(other_chunks) => {
+                                    common_chunks = common_chunks
+                                        .intersection(&other_chunks.keys().copied().collect())
+                                        .copied()
+                                        .collect();
+                                }
+                                None => common_chunks.clear(),
+                            }
+                        }
+
+                        if !common_chunks.is_empty() {
+                            let mut positions: HashMap<usize, Vec<usize>> = HashMap::new();
+                            for word_chunks in &per_word {
+                                if let Some(word_positions) = word_chunks.get(doc_id) {
+                                    for &chunk_id in &common_chunks {
+                                        if let Some(p) = word_positions.get(&chunk_id) {
+                                            positions
+                                                .entry(chunk_id)
+                                                .or_insert_with(Vec::new)
+                                                .extend(p);
+                                        }
+                                    }
+                                }
+                            }
+                            merged.insert(doc_id.clone(), (positions, words.len()));
+                        }
+                    }
+                }
+            }
+        }
+
+        merged
+    }
+
+    fn fold_term_postings(&self, by_doc: &HashMap<String, Postings>, merged: &mut DocMatches) {
+        for (doc_id, postings) in by_doc {
+            let entry = merged
+                .entry(doc_id.clone())
+                .or_insert_with(|| (HashMap::new(), 0));
+            for (chunk_id, positions) in postings {
+                entry
+                    .0
+                    .entry(*chunk_id)
+                    .or_insert_with(Vec::new)
+                    .extend(positions);
+            }
+            // One leaf is one query term, however many indexed terms it
+            // matched in this doc.
+            entry.1 = 1;
+        }
+    }
+}
+
+/// Lowercase tokenizer shared by indexing and query parsing, splitting on
+/// every character that isn't Unicode alphanumeric.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
@@ -0,0 +1,82 @@
+use crate::error::Result;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+/// How long a term stays in the rolling trend window before it expires.
+const TREND_TTL_SECS: i64 = 24 * 60 * 60;
+/// How many of the highest-scoring terms are kept per window.
+const TOP_POOL_SIZE: isize = 500;
+
+/// Aggregates keywords/terms across processed documents into a rolling
+/// trend index in Redis, the way a firehose tagger tracks trending tags.
+pub struct TrendTracker {
+    redis: Arc<ConnectionManager>,
+}
+
+impl TrendTracker {
+    pub fn new(redis: Arc<ConnectionManager>) -> Self {
+        Self { redis }
+    }
+
+    /// Record that `terms` occurred in `doc_id`, bumping each term's score
+    /// in the current time-window's sorted set and refreshing its TTL.
+    #[instrument(skip(self, terms))]
+    pub async fn record_terms(&self, doc_id: &str, terms: &[String]) -> Result<()> {
+        let window = Self::current_window();
+        let key = Self::window_key(window);
+        let mut conn = (*self.redis).clone();
+
+        for term in terms {
+            conn.zincr(&key, term, 1.0).await?;
+        }
+        conn.expire(&key, TREND_TTL_SECS).await?;
+        conn.zremrangebyrank(&key, 0, -(TOP_POOL_SIZE + 1)).await?;
+
+        info!("Recorded {} terms for document {} in window {}", terms.len(), doc_id, window);
+        Ok(())
+    }
+
+    /// Return the top `limit` terms for the window containing `window`
+    /// (a unix timestamp), ranked by time-decayed score, highest first.
+    ///
+    /// Scores decay with a 6-hour half-life by the window's age, so an older
+    /// window's scores come back smaller than the same counts in the current
+    /// one. Every term in a window gets the same factor, so the order within
+    /// the returned window is its raw-count order.
+    #[instrument(skip(self))]
+    pub async fn top_trends(&self, window: i64, limit: isize) -> Result<Vec<(String, f64)>> {
+        const HALF_LIFE_HOURS: f64 = 6.0;
+
+        let key = Self::window_key(window);
+        let mut conn = (*self.redis).clone();
+
+        let raw: Vec<(String, f64)> = conn.zrevrange_withscores(&key, 0, -1).await?;
+
+        let bucket = window / 3600;
+        let now_bucket = Self::current_window() / 3600;
+        let age_hours = (now_bucket - bucket).max(0) as f64;
+        let decay = 0.5_f64.powf(age_hours / HALF_LIFE_HOURS);
+
+        let mut decayed: Vec<(String, f64)> = raw
+            .into_iter()
+            .map(|(term, score)| (term, score * decay))
+            .collect();
+
+        decayed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        decayed.truncate(limit.max(0) as usize);
+
+        Ok(decayed)
+    }
+
+    fn current_window() -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+
+    fn window_key(window: i64) -> String {
+        // Bucket into hourly windows so trends roll over smoothly.
+        let bucket = window / 3600;
+        format!("trends:{}", bucket)
+    }
+}
@@ -0,0 +1,213 @@
+use crate::error::Result;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{info, instrument, warn};
+
+/// What a `Worker::work` call accomplished, so `WorkerManager` knows
+/// whether to loop again immediately, back off, or stop driving it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerState {
+    /// Processed an item; call `work` again right away.
+    Busy,
+    /// Found nothing to do; back off before calling `work` again.
+    Idle,
+    /// Permanently finished; stop calling `work`.
+    Done,
+}
+
+/// A unit of repeatable work a `WorkerManager` drives to completion —
+/// dequeue-and-process-one-item being the common case.
+#[async_trait]
+pub trait Worker: Send {
+    async fn work(&mut self) -> Result<WorkerState>;
+}
+
+/// Cooperative shutdown signal shared between a `WorkerManager` and every
+/// worker it spawned. `shutdown()` flips a flag and wakes any worker
+/// currently backing off on `Idle`, so it notices and exits between items
+/// instead of mid-item.
+#[derive(Clone)]
+pub struct ShutdownToken {
+    flag: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl ShutdownToken {
+    pub fn new() -> Self {
+        Self {
+            flag: Arc::new(AtomicBool::new(false)),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    pub fn shutdown(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+        self.notify.notify_waiters();
+    }
+
+    /// Back off for `duration`, waking early if `shutdown` is called.
+    async fn wait_or_shutdown(&self, duration: Duration) {
+        // Create the `Notified` future before checking the flag: it receives
+        // `notify_waiters` from creation on, so a shutdown landing between
+        // the check and the `select!` isn't missed.
+        let notified = self.notify.notified();
+        if self.is_shutdown() {
+            return;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => {}
+            _ = notified => {}
+        }
+    }
+}
+
+impl Default for ShutdownToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Caps a worker loop at a target rate by measuring how long each `work`
+/// call actually took and sleeping off whatever's left of the interval
+/// that rate implies, so a burst of cheap items doesn't translate into a
+/// burst of requests against whatever the worker calls out to.
+struct Tranquilizer {
+    target_interval: Duration,
+}
+
+impl Tranquilizer {
+    fn new(target_per_second: f64) -> Self {
+        Self {
+            target_interval: Duration::from_secs_f64(1.0 / target_per_second.max(0.001)),
+        }
+    }
+
+    async fn throttle(&self, elapsed: Duration) {
+        if let Some(remaining) = self.target_interval.checked_sub(elapsed) {
+            tokio::time::sleep(remaining).await;
+        }
+    }
+}
+
+/// Pool configuration for `WorkerManager::spawn`.
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// How many workers run concurrently against the same queue.
+    pub pool_size: usize,
+    /// How long an `Idle` worker backs off before calling `work` again.
+    pub idle_backoff: Duration,
+    /// Cap each worker's throughput at this many `Busy` items/sec.
+    /// `None` disables throttling.
+    pub target_rate: Option<f64>,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: 1,
+            idle_backoff: Duration::from_secs(1),
+            target_rate: None,
+        }
+    }
+}
+
+/// Spawns and supervises a pool of `Worker`s against a shared
+/// `ShutdownToken`, so queue-draining loops get concurrency control,
+/// idle backoff, and graceful shutdown without each one reimplementing it.
+pub struct WorkerManager {
+    shutdown: ShutdownToken,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self {
+            shutdown: ShutdownToken::new(),
+        }
+    }
+
+    /// A clone of the shutdown token, so callers can trigger shutdown
+    /// without holding onto the `WorkerManager` itself.
+    pub fn shutdown_token(&self) -> ShutdownToken {
+        self.shutdown.clone()
+    }
+
+    /// Tell every worker spawned by this manager to finish its current
+    /// item and stop.
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown();
+    }
+
+    /// Spawn `config.pool_size` workers, each built by calling
+    /// `make_worker` once (a `Worker` isn't assumed to be `Clone`), and
+    /// drive each with `run_worker` until it returns `Done` or shutdown
+    /// fires.
+    pub fn spawn<W, F>(&self, config: WorkerConfig, mut make_worker: F) -> Vec<JoinHandle<()>>
+    where
+        W: Worker + 'static,
+        F: FnMut(usize) -> W,
+    {
+        (0..config.pool_size)
+            .map(|id| {
+                let worker = make_worker(id);
+                let shutdown = self.shutdown.clone();
+                let config = config.clone();
+                tokio::spawn(run_worker(id, worker, config, shutdown))
+            })
+            .collect()
+    }
+}
+
+impl Default for WorkerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[instrument(skip(worker, config, shutdown))]
+async fn run_worker<W: Worker>(
+    id: usize,
+    mut worker: W,
+    config: WorkerConfig,
+    shutdown: ShutdownToken,
+) {
+    let tranquilizer = config.target_rate.map(Tranquilizer::new);
+
+    loop {
+        if shutdown.is_shutdown() {
+            info!("Worker {} shutting down", id);
+            break;
+        }
+
+        let start = Instant::now();
+        let state = match worker.work().await {
+            Ok(state) => state,
+            Err(e) => {
+                warn!("Worker {} errored: {}", id, e);
+                WorkerState::Idle
+            }
+        };
+
+        match state {
+            WorkerState::Busy => {
+                if let Some(tranquilizer) = &tranquilizer {
+                    tranquilizer.throttle(start.elapsed()).await;
+                }
+            }
+            WorkerState::Idle => {
+                shutdown.wait_or_shutdown(config.idle_backoff).await;
+            }
+            WorkerState::Done => {
+                info!("Worker {} done", id);
+                break;
+            }
+        }
+    }
+}